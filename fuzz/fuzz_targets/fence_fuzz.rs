@@ -0,0 +1,35 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use time_tracker::domain::config::ParseConfig;
+use time_tracker::parsing;
+
+// Whatever is inside a code fence must never change the day or the tracked time.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(fuzzed_block) = std::str::from_utf8(data) {
+        // A line that closes the fence ends the block, which is not what is fuzzed here
+        if fuzzed_block
+            .lines()
+            .any(|line| line.trim().starts_with("```"))
+        {
+            return;
+        }
+
+        let content = format!(
+            "## TT 2020-01-01\n- #dev 1h Task description\n```\n{}\n```\n- #dev 30m Task description",
+            fuzzed_block
+        );
+
+        let result = parsing::process_content(&content, "fuzz.md", &ParseConfig::default());
+        let tracked = result
+            .time_entries
+            .expect("entries around the fence should be tracked");
+        let dates: Vec<String> = tracked
+            .entries_by_date
+            .keys()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(dates, ["2020-01-01"]);
+        assert_eq!(tracked.total_minutes, 90);
+    }
+});
@@ -1,7 +1,10 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
-use crate::domain::reporting::{BreakdownUnit, OutputLimit};
+use crate::config::Config;
+use crate::domain::config::ReportConfig;
+use crate::domain::diagnostics::DiagnosticFormat;
+use crate::domain::reporting::{BreakdownUnit, GroupBy, OutputLimit};
 use crate::domain::tags::TagFilter;
 use crate::domain::time::Clock;
 use crate::domain::ParseError;
@@ -13,8 +16,11 @@ pub mod statistics;
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Simple time tracking from markdown files")]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Input file to process
-    #[arg(short, long, value_name = "FILE")]
+    #[arg(short, long, value_name = "FILE", global = true)]
     pub input: Option<PathBuf>,
 
     /// Show verbose output
@@ -41,6 +47,18 @@ pub struct Args {
     #[arg(long)]
     pub exclude_tags: Option<String>,
 
+    /// Metadata filter, e.g. `client=acme,billable=no`
+    #[arg(long, value_name = "KEY=VALUE")]
+    pub meta: Option<String>,
+
+    /// Only include entries mentioning this person, e.g. `alice` for `@alice`
+    #[arg(long, value_name = "NAME")]
+    pub person: Option<String>,
+
+    /// Leave out entries on days marked `[holiday]` or `[sick]` in their TT header
+    #[arg(long)]
+    pub exclude_leave: bool,
+
     /// From date filter value
     #[arg(short, long, value_name = "YYYY-MM-DD")]
     pub from: Option<String>,
@@ -51,12 +69,52 @@ pub struct Args {
     )]
     period: Option<String>,
 
-    #[arg(long, value_name = "text, markdown", default_value = "text")]
+    #[arg(long, value_name = "text, markdown, json", default_value = "text")]
     pub format: Option<String>,
 
+    /// How warnings and `check` problems are written; json writes one object per line
+    #[arg(long, value_name = "text, json", default_value = "text", global = true)]
+    pub diagnostics: Option<String>,
+
     #[arg(short, long, value_name = "day, d, week, month, year, auto")]
     pub breakdown: Option<String>,
 
+    /// Roll up hierarchical tags (#client/acme/meeting) into a tree of the given depth
+    #[arg(long, value_name = "N")]
+    pub depth: Option<usize>,
+
+    /// Date entries by the file name or front matter of daily notes, e.g. `2025-01-15.md`
+    #[arg(long)]
+    pub daily_notes: bool,
+
+    /// Group totals by a metadata key instead of by project, e.g. `client`
+    #[arg(long, value_name = "KEY")]
+    pub group_by: Option<String>,
+
+    /// Show time per person mentioned with `@name` instead of per project
+    #[arg(long)]
+    pub people: bool,
+
+    /// Show time per ticket key found in descriptions, e.g. `PROJ-1234`, instead of per project
+    #[arg(long)]
+    pub by_ticket: bool,
+
+    /// Show estimated tasks (`est:3h`) with the time tracked on them
+    #[arg(long)]
+    pub estimates: bool,
+
+    /// List every entry in date order with the file and line it was written on
+    #[arg(long)]
+    pub log: bool,
+
+    /// Fail on any problem in the input, printing the problems instead of the report
+    #[arg(long, global = true)]
+    pub strict: bool,
+
+    /// Configuration file (JSON)
+    #[arg(long, value_name = "FILE", global = true)]
+    pub config: Option<PathBuf>,
+
     /// Start web server mode
     #[arg(long)]
     pub web: bool,
@@ -70,6 +128,12 @@ pub struct Args {
     pub host: String,
 }
 
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Check every file under the input path for problems, failing on errors, or on any problem with --strict
+    Check,
+}
+
 impl Args {
     /// Parses command line arguments and validates them.
     ///
@@ -96,8 +160,13 @@ impl Args {
             );
         }
 
+        // Report options do not apply to checking
+        if self.command == Some(Command::Check) {
+            return Ok(());
+        }
+
         // Check if details is specified without tags
-        if self.details && self.tags.is_none() && self.project.is_none() {
+        if self.details && self.tags.is_none() && self.project.is_none() && !self.groups_time() {
             return Err("--details flag requires --tags to be specified".to_string());
         }
 
@@ -108,6 +177,62 @@ impl Args {
             );
         }
 
+        if self.depth == Some(0) {
+            return Err("--depth must be at least 1".to_string());
+        }
+
+        if self.people && self.group_by.is_some() {
+            return Err("--people cannot be combined with --group-by".to_string());
+        }
+
+        if self.group_by.is_some() && self.depth.is_some() {
+            return Err("--group-by cannot be combined with --depth".to_string());
+        }
+
+        if self.people && self.depth.is_some() {
+            return Err("--people cannot be combined with --depth".to_string());
+        }
+
+        if self.by_ticket && (self.people || self.group_by.is_some() || self.depth.is_some()) {
+            return Err(
+                "--by-ticket cannot be combined with --group-by, --people or --depth".to_string(),
+            );
+        }
+
+        if self.estimates
+            && (self.details
+                || self.breakdown.is_some()
+                || self.groups_time()
+                || self.depth.is_some())
+        {
+            return Err(
+                "--estimates cannot be combined with --details, --breakdown, --group-by, --people or --depth"
+                    .to_string(),
+            );
+        }
+
+        if self.log
+            && (self.details
+                || self.breakdown.is_some()
+                || self.groups_time()
+                || self.depth.is_some()
+                || self.estimates)
+        {
+            return Err(
+                "--log cannot be combined with --details, --breakdown, --group-by, --people, --depth or --estimates"
+                    .to_string(),
+            );
+        }
+
+        if let Some(meta) = &self.meta {
+            if meta
+                .split(',')
+                .any(|pair| parse_metadata_pair(pair).is_none())
+            {
+                return Err(format!("--meta expects KEY=VALUE pairs: {meta}"));
+            }
+        }
+
         Ok(())
     }
 
@@ -126,6 +251,39 @@ impl Args {
         }
     }
 
+    /// Parses the metadata filter from the command line arguments.
+    #[must_use]
+    pub fn metadata_filter(&self) -> Vec<(String, String)> {
+        self.meta
+            .as_deref()
+            .map(|meta| meta.split(',').filter_map(parse_metadata_pair).collect())
+            .unwrap_or_default()
+    }
+
+    /// The person filter, without a leading `@`.
+    #[must_use]
+    pub fn person_filter(&self) -> Option<&str> {
+        self.person
+            .as_deref()
+            .map(|person| person.trim_start_matches('@'))
+    }
+
+    /// How time is grouped instead of per project, if at all.
+    #[must_use]
+    pub fn grouping(&self, reporting: &ReportConfig) -> Option<GroupBy> {
+        if self.people {
+            Some(GroupBy::People)
+        } else if self.by_ticket {
+            Some(GroupBy::Tickets(reporting.ticket_url.clone()))
+        } else {
+            self.group_by.clone().map(GroupBy::Metadata)
+        }
+    }
+
+    fn groups_time(&self) -> bool {
+        self.people || self.by_ticket || self.group_by.is_some()
+    }
+
     #[must_use]
     pub fn include_details(&self) -> bool {
         self.project.is_some() || self.details
@@ -186,6 +344,30 @@ impl Args {
         }
     }
 
+    /// Loads the configuration file, or the defaults when none is given,
+    /// with the settings given on the command line applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` if the configuration file cannot be read or is invalid.
+    pub fn config(&self) -> Result<Config, ParseError> {
+        let mut config = self
+            .config
+            .as_deref()
+            .map_or_else(|| Ok(Config::default()), Config::load)?;
+        config.parsing.daily_notes |= self.daily_notes;
+        config.diagnostics.strict |= self.strict;
+        Ok(config)
+    }
+
+    #[must_use]
+    pub fn diagnostic_format(&self) -> DiagnosticFormat {
+        self.diagnostics
+            .as_deref()
+            .map(DiagnosticFormat::parse)
+            .unwrap_or_default()
+    }
+
     #[must_use]
     pub fn formatter(&self) -> Box<dyn Formatter> {
         <dyn Formatter>::from_str(self.format.as_ref())
@@ -217,3 +399,9 @@ impl Args {
         })
     }
 }
+
+fn parse_metadata_pair(pair: &str) -> Option<(String, String)> {
+    let (key, value) = pair.split_once('=')?;
+    let (key, value) = (key.trim(), value.trim());
+    (!key.is_empty() && !value.is_empty()).then(|| (key.to_string(), value.to_string()))
+}
@@ -1,4 +1,4 @@
-use crate::cli::Args;
+use crate::cli::{Args, Command};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -37,6 +37,10 @@ impl StatisticsCollector {
     pub fn from_args(args: &Args) -> StatRecord {
         let mut flags_used = Vec::new();
 
+        if args.command == Some(Command::Check) {
+            flags_used.push("check".to_string());
+        }
+
         if args.input.is_some() {
             flags_used.push("input".to_string());
         }
@@ -67,9 +71,48 @@ impl StatisticsCollector {
         if args.format.as_deref() != Some("text") {
             flags_used.push("format".to_string());
         }
+        if args.diagnostics.as_deref() != Some("text") {
+            flags_used.push("diagnostics".to_string());
+        }
         if args.breakdown.is_some() {
             flags_used.push("breakdown".to_string());
         }
+        if args.depth.is_some() {
+            flags_used.push("depth".to_string());
+        }
+        if args.meta.is_some() {
+            flags_used.push("meta".to_string());
+        }
+        if args.group_by.is_some() {
+            flags_used.push("group-by".to_string());
+        }
+        if args.person.is_some() {
+            flags_used.push("person".to_string());
+        }
+        if args.exclude_leave {
+            flags_used.push("exclude-leave".to_string());
+        }
+        if args.people {
+            flags_used.push("people".to_string());
+        }
+        if args.by_ticket {
+            flags_used.push("by-ticket".to_string());
+        }
+        if args.estimates {
+            flags_used.push("estimates".to_string());
+        }
+        if args.log {
+            flags_used.push("log".to_string());
+        }
+        if args.strict {
+            flags_used.push("strict".to_string());
+        }
+        if args.daily_notes {
+            flags_used.push("daily-notes".to_string());
+        }
+        if args.config.is_some() {
+            flags_used.push("config".to_string());
+        }
         if args.web {
             flags_used.push("web".to_string());
         }
@@ -186,7 +229,7 @@ pub fn read_flag_statistics() -> std::io::Result<FlagStatistics> {
         })
         .collect();
 
-    flags.sort_by(|a, b| b.count.cmp(&a.count));
+    flags.sort_by_key(|flag| std::cmp::Reverse(flag.count));
 
     Ok(FlagStatistics {
         flags,
@@ -0,0 +1,172 @@
+//! User configuration, read from a JSON file passed with `--config`.
+//!
+//! ```json
+//! {
+//!   "parsing": { "day-length": "7h30m" },
+//!   "reporting": { "ticket-url": "https://jira.example.com/browse/{ticket}" },
+//!   "diagnostics": { "strict": true, "suppress": [{ "code": "TT011" }] },
+//!   "rules": { "max-day": "14h", "weekends": true }
+//! }
+//! ```
+//!
+//! Every setting is optional and defaults to the behaviour without a configuration file.
+
+use serde::Deserialize;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use crate::domain::config::{DiagnosticsConfig, ParseConfig, ReportConfig, RulesConfig};
+use crate::domain::ParseError;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    pub parsing: ParseConfig,
+    pub reporting: ReportConfig,
+    pub diagnostics: DiagnosticsConfig,
+    pub rules: RulesConfig,
+}
+
+impl Config {
+    /// Reads the configuration from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::ErrorReading` if the file cannot be read and
+    /// `ParseError::InvalidConfig` if its content is not a valid configuration.
+    pub fn load(path: &Path) -> Result<Self, ParseError> {
+        let content = read_to_string(path).map_err(|err| {
+            ParseError::ErrorReading(format!("Failed to read {}: {err}", path.display()))
+        })?;
+        Self::parse(&content)
+            .map_err(|err| ParseError::InvalidConfig(format!("{}: {err}", path.display())))
+    }
+
+    fn parse(content: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::diagnostics::Diagnostic;
+    use crate::domain::rules::Rule;
+    use crate::domain::Location;
+
+    #[test]
+    fn config_should_default_when_empty() {
+        assert_eq!(Config::parse("{}").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn config_should_read_day_length() {
+        let config = Config::parse(r#"{ "parsing": { "day-length": "7h30m" } }"#).unwrap();
+
+        assert_eq!(config.parsing.day_minutes, Some(450));
+    }
+
+    #[test]
+    fn config_should_read_pomodoro_length_and_units() {
+        let config = Config::parse(
+            r#"{ "parsing": { "pomodoro-length": "25m", "units": { "block": "45m" } } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.parsing.unit_minutes("p"), Some(25));
+        assert_eq!(config.parsing.unit_minutes("block"), Some(45));
+    }
+
+    #[test]
+    fn config_should_reject_units_replacing_built_in_ones() {
+        assert!(Config::parse(r#"{ "parsing": { "units": { "h": "50m" } } }"#).is_err());
+        assert!(Config::parse(r#"{ "parsing": { "units": { "b2": "50m" } } }"#).is_err());
+    }
+
+    #[test]
+    fn config_should_read_ticket_settings() {
+        let config = Config::parse(
+            r##"{ "parsing": { "ticket-pattern": "#(\\d+)" },
+                 "reporting": { "ticket-url": "https://example.com/issues/{ticket}" } }"##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.parsing.ticket_pattern.unwrap().find_in("fix #12"),
+            ["12"]
+        );
+        assert_eq!(
+            config.reporting.ticket_url.unwrap().link("12"),
+            "https://example.com/issues/12"
+        );
+    }
+
+    #[test]
+    fn config_should_reject_invalid_ticket_pattern() {
+        assert!(Config::parse(r#"{ "parsing": { "ticket-pattern": "([A-Z" } }"#).is_err());
+    }
+
+    #[test]
+    fn config_should_read_diagnostics_settings() {
+        let config = Config::parse(
+            r#"{ "diagnostics": { "strict": true,
+                 "suppress": [{ "code": "TT011" }, { "code": "TT008", "file": "archive" }] } }"#,
+        )
+        .unwrap();
+
+        assert!(config.diagnostics.strict);
+        assert_eq!(config.diagnostics.suppress.len(), 2);
+    }
+
+    #[test]
+    fn suppressions_should_match_code_and_file_or_directory() {
+        let config = Config::parse(
+            r#"{ "diagnostics": { "suppress": [{ "code": "TT008", "file": "archive/" }] } }"#,
+        )
+        .unwrap();
+        let diagnostic = |file: &str| {
+            Diagnostic::from(&ParseError::Located {
+                error: Box::new(ParseError::MultipleDurations(String::new())),
+                location: Location {
+                    file: file.to_string(),
+                    line: 1,
+                },
+                span: None,
+            })
+        };
+
+        assert!(!config.diagnostics.reports(&diagnostic("archive/2024.md")));
+        assert!(config.diagnostics.reports(&diagnostic("archive-2024.md")));
+        assert!(config
+            .diagnostics
+            .reports(&Diagnostic::from(&ParseError::MissingTime(String::new()))));
+    }
+
+    #[test]
+    fn config_should_reject_suppression_without_code_or_file() {
+        assert!(Config::parse(r#"{ "diagnostics": { "suppress": [{}] } }"#).is_err());
+    }
+
+    #[test]
+    fn config_should_read_rules() {
+        let config = Config::parse(
+            r#"{ "rules": { "max-day": "14h", "max-entry": null, "weekends": true } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.rules.rules(),
+            [
+                Rule::MaxDayMinutes(14 * 60),
+                Rule::Duplicates,
+                Rule::Weekends,
+                Rule::FutureDates
+            ]
+        );
+    }
+
+    #[test]
+    fn config_should_reject_unknown_settings() {
+        assert!(Config::parse(r#"{ "parsing": { "week-length": "5d" } }"#).is_err());
+    }
+}
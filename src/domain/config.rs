@@ -0,0 +1,428 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer};
+
+use super::diagnostics::Diagnostic;
+use super::duration::parse_duration;
+use super::rules::Rule;
+use super::tickets::{TicketPattern, TicketUrl};
+
+const DEFAULT_POMODORO_MINUTES: u32 = 30;
+const DEFAULT_MAX_DAY_MINUTES: u32 = 24 * 60;
+const DEFAULT_MAX_ENTRY_MINUTES: u32 = 16 * 60;
+/// Calendar dates (`2025-01-15`) and ISO week dates (`2025-W03-3`).
+const DEFAULT_DATE_FORMATS: [&str; 2] = ["%Y-%m-%d", "%G-W%V-%u"];
+
+/// Settings that change how entry lines are read.
+///
+/// ```json
+/// { "parsing": { "day-length": "7h30m", "time-marker": "first", "entry-markers": ["-", "*"] } }
+/// ```
+///
+/// Units of other lengths can be added, e.g. `{ "units": { "block": "45m" } }` for `2block`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ParseConfig {
+    /// Minutes in one `d` (day) unit; without a day length `d` is no unit, so that words
+    /// such as `3d` in a description are not read as days.
+    #[serde(rename = "day-length", deserialize_with = "deserialize_day_length")]
+    pub day_minutes: Option<u32>,
+    /// Minutes in one `p` (pomodoro) unit.
+    #[serde(rename = "pomodoro-length", deserialize_with = "deserialize_minutes")]
+    pub pomodoro_minutes: u32,
+    /// Minutes in each user-defined unit, e.g. `block` for `2block`.
+    #[serde(deserialize_with = "deserialize_units")]
+    pub units: BTreeMap<String, u32>,
+    pub time_marker: TimeMarker,
+    pub tags: TagGrammar,
+    /// List markers that start an entry line, `-` only by default.
+    pub entry_markers: Vec<EntryMarker>,
+    /// `chrono` formats tried in order to read the date of a `# TT <date>` header.
+    pub date_formats: Vec<String>,
+    /// Reads each file as a daily note, dated by its front matter `date:` or its file name,
+    /// so entries need no TT header.
+    pub daily_notes: bool,
+    /// Finds ticket keys in descriptions, e.g. Jira-style keys such as `PROJ-1234`;
+    /// no tickets are read unless a pattern is configured.
+    pub ticket_pattern: Option<TicketPattern>,
+    /// Keys read from `key:value` tokens as metadata, e.g. `client` for `client:acme`;
+    /// tokens with other keys stay in the description.
+    pub metadata_keys: Vec<String>,
+}
+
+impl Default for ParseConfig {
+    fn default() -> Self {
+        Self {
+            day_minutes: None,
+            pomodoro_minutes: DEFAULT_POMODORO_MINUTES,
+            units: BTreeMap::new(),
+            time_marker: TimeMarker::default(),
+            tags: TagGrammar::default(),
+            entry_markers: vec![EntryMarker::Dash],
+            date_formats: DEFAULT_DATE_FORMATS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            daily_notes: false,
+            ticket_pattern: None,
+            metadata_keys: Vec::new(),
+        }
+    }
+}
+
+/// Settings that change how reports are shown.
+///
+/// ```json
+/// { "reporting": { "ticket-url": "https://jira.example.com/browse/{ticket}" } }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ReportConfig {
+    /// Link template for ticket keys, with `{ticket}` replaced by the key.
+    pub ticket_url: Option<TicketUrl>,
+}
+
+/// Settings on how parse problems are treated.
+///
+/// ```json
+/// { "diagnostics": { "strict": true, "suppress": [{ "code": "TT011" }, { "file": "archive" }] } }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DiagnosticsConfig {
+    /// Fail runs with any problem before printing a report, as `--strict` does.
+    pub strict: bool,
+    /// Problems that are neither reported nor fail a run.
+    #[serde(deserialize_with = "deserialize_suppressions")]
+    pub suppress: Vec<Suppression>,
+}
+
+impl DiagnosticsConfig {
+    /// Whether the diagnostic is reported, i.e. no suppression matches it.
+    #[must_use]
+    pub fn reports(&self, diagnostic: &Diagnostic) -> bool {
+        !self
+            .suppress
+            .iter()
+            .any(|suppression| suppression.matches(diagnostic))
+    }
+}
+
+/// Sanity rules checked on the tracked days, reported as warnings.
+///
+/// ```json
+/// { "rules": { "max-day": "14h", "max-entry": null, "weekends": true } }
+/// ```
+///
+/// A `null` limit turns the rule off.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RulesConfig {
+    /// Most minutes a day should total, 24 hours by default.
+    #[serde(deserialize_with = "deserialize_limit")]
+    pub max_day: Option<u32>,
+    /// Most minutes a single entry should take, 16 hours by default.
+    #[serde(deserialize_with = "deserialize_limit")]
+    pub max_entry: Option<u32>,
+    /// Flags an entry that is the same as another one on the same date.
+    pub duplicates: bool,
+    /// Flags time tracked on Saturdays and Sundays; off by default.
+    pub weekends: bool,
+    /// Flags time tracked on days after today.
+    pub future_dates: bool,
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        Self {
+            max_day: Some(DEFAULT_MAX_DAY_MINUTES),
+            max_entry: Some(DEFAULT_MAX_ENTRY_MINUTES),
+            duplicates: true,
+            weekends: false,
+            future_dates: true,
+        }
+    }
+}
+
+impl RulesConfig {
+    /// The rules that are turned on.
+    #[must_use]
+    pub fn rules(&self) -> Vec<Rule> {
+        self.max_day
+            .map(Rule::MaxDayMinutes)
+            .into_iter()
+            .chain(self.max_entry.map(Rule::MaxEntryMinutes))
+            .chain(self.duplicates.then_some(Rule::Duplicates))
+            .chain(self.weekends.then_some(Rule::Weekends))
+            .chain(self.future_dates.then_some(Rule::FutureDates))
+            .collect()
+    }
+}
+
+/// Problems to ignore: those with a code, those in a file or directory, or both.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Suppression {
+    /// A diagnostic code, e.g. `TT011`.
+    pub code: Option<String>,
+    /// A file or directory relative to the input path, e.g. `2024/03.md` or `archive`.
+    pub file: Option<String>,
+}
+
+impl Suppression {
+    fn matches(&self, diagnostic: &Diagnostic) -> bool {
+        let code_matches = self
+            .code
+            .as_ref()
+            .is_none_or(|code| code.eq_ignore_ascii_case(diagnostic.code));
+        let file_matches = self.file.as_ref().is_none_or(|file| {
+            let file = file.trim_end_matches('/');
+            diagnostic.location.as_ref().is_some_and(|location| {
+                location.file == file
+                    || location
+                        .file
+                        .strip_prefix(file)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+        });
+        code_matches && file_matches
+    }
+}
+
+/// Which `#word` tokens are read as tags.
+///
+/// Rejected tokens, e.g. issue numbers like `#4521`, stay in the description.
+/// The defaults accept every `#word`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TagGrammar {
+    /// Characters allowed besides letters and digits, any character when not set.
+    pub allowed_chars: Option<String>,
+    /// Minimum number of letters a tag must contain.
+    pub min_letters: usize,
+    /// Rejects tags made up of digits only.
+    pub ignore_numeric: bool,
+    /// Rejects tags once the description has started.
+    pub leading_only: bool,
+}
+
+impl TagGrammar {
+    /// Whether a tag name (without `#`) is accepted by this grammar.
+    #[must_use]
+    pub fn accepts(&self, tag: &str) -> bool {
+        let allowed = |c: char| {
+            c.is_alphanumeric()
+                || self
+                    .allowed_chars
+                    .as_ref()
+                    .is_none_or(|chars| chars.contains(c))
+        };
+        let letters = tag.chars().filter(|c| c.is_alphabetic()).count();
+        let numeric = !tag.is_empty() && tag.chars().all(|c| c.is_ascii_digit());
+
+        tag.chars().all(allowed) && letters >= self.min_letters && !(self.ignore_numeric && numeric)
+    }
+}
+
+/// A list marker that can start an entry line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum EntryMarker {
+    /// `- #dev 1h`
+    #[serde(rename = "-")]
+    Dash,
+    /// `* #dev 1h`
+    #[serde(rename = "*")]
+    Star,
+    /// `+ #dev 1h`
+    #[serde(rename = "+")]
+    Plus,
+    /// `1. #dev 1h` or `1) #dev 1h`
+    #[serde(rename = "numbered")]
+    Numbered,
+}
+
+impl EntryMarker {
+    /// Returns the rest of the line when it starts with this marker followed by a space.
+    pub(crate) fn strip_from(self, line: &str) -> Option<&str> {
+        match self {
+            EntryMarker::Dash => line.strip_prefix("- "),
+            EntryMarker::Star => line.strip_prefix("* "),
+            EntryMarker::Plus => line.strip_prefix("+ "),
+            EntryMarker::Numbered => {
+                let rest = line.trim_start_matches(|c: char| c.is_ascii_digit());
+                if rest.len() == line.len() {
+                    return None;
+                }
+                rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") "))
+            }
+        }
+    }
+}
+
+/// Which tokens of an entry line count as time.
+///
+/// Stricter markers keep numbers in descriptions, e.g. "fix 2h timeout bug", out of the totals.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeMarker {
+    /// Every duration on the line is added up: `- #dev 1h 30m`.
+    #[default]
+    Any,
+    /// Only the first duration counts, later ones are description: `- #dev 1h 30m fix 2h bug`.
+    First,
+    /// Only durations prefixed with `@` count: `- #dev @2h fix 2h timeout bug`.
+    Sigil,
+}
+
+impl TimeMarker {
+    /// Reads a marker from its configuration name, e.g. `first`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "any" => Some(TimeMarker::Any),
+            "first" => Some(TimeMarker::First),
+            "sigil" => Some(TimeMarker::Sigil),
+            _ => None,
+        }
+    }
+
+    /// Whether the n-th (1-based) duration on a line adds to the entry's time.
+    ///
+    /// Adjacent time tokens, such as `1h 30m`, are a single duration.
+    pub(crate) fn counts_duration(self, nth: usize) -> bool {
+        match self {
+            TimeMarker::Any | TimeMarker::Sigil => true,
+            TimeMarker::First => nth == 1,
+        }
+    }
+}
+
+impl ParseConfig {
+    /// Minutes a single duration unit stands for, e.g. `60` for `h`.
+    #[must_use]
+    pub fn unit_minutes(&self, unit: &str) -> Option<u32> {
+        match unit {
+            "m" | "min" | "mins" | "minute" | "minutes" => Some(1),
+            "h" | "hr" | "hrs" | "hour" | "hours" => Some(60),
+            "p" | "pomodoro" | "pomodoros" => Some(self.pomodoro_minutes),
+            "d" | "day" | "days" => self.day_minutes,
+            _ => self.units.get(unit).copied(),
+        }
+    }
+
+    /// Units whose length is not the default, i.e. a changed `p`, the `d` of a configured
+    /// day length and the user-defined ones.
+    #[must_use]
+    pub fn unit_definitions(&self) -> Vec<UnitDefinition> {
+        let changed_built_in = [
+            (self.pomodoro_minutes != DEFAULT_POMODORO_MINUTES)
+                .then_some(("p", self.pomodoro_minutes)),
+            self.day_minutes.map(|minutes| ("d", minutes)),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|(unit, minutes)| (unit.to_string(), minutes));
+        let user_defined = self
+            .units
+            .iter()
+            .map(|(unit, &minutes)| (unit.clone(), minutes));
+        changed_built_in
+            .chain(user_defined)
+            .map(|(unit, minutes)| UnitDefinition { unit, minutes })
+            .collect()
+    }
+}
+
+/// The length of a duration unit, e.g. `block` = 45 minutes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitDefinition {
+    pub unit: String,
+    pub minutes: u32,
+}
+
+fn deserialize_minutes<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    match parse_duration(&value, &ParseConfig::default()) {
+        Ok(Some(minutes)) if minutes > 0 => Ok(minutes),
+        _ => Err(serde::de::Error::custom(format!(
+            "invalid duration: {value}"
+        ))),
+    }
+}
+
+/// Reads the `day-length`, which turns the `d` unit on.
+fn deserialize_day_length<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_minutes(deserializer).map(Some)
+}
+
+/// Reads a rule's limit, e.g. `"14h"`, or `null` for none.
+fn deserialize_limit<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) => match parse_duration(&value, &ParseConfig::default()) {
+            Ok(Some(minutes)) if minutes > 0 => Ok(Some(minutes)),
+            _ => Err(serde::de::Error::custom(format!(
+                "invalid duration: {value}"
+            ))),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Reads user-defined units, e.g. `{ "block": "45m" }`; names are letters only and
+/// cannot replace a built-in unit, `d` included.
+fn deserialize_units<'de, D>(deserializer: D) -> Result<BTreeMap<String, u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let units = BTreeMap::<String, String>::deserialize(deserializer)?;
+    units
+        .into_iter()
+        .map(|(unit, length)| {
+            if unit.is_empty() || !unit.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(serde::de::Error::custom(format!(
+                    "unit names should be letters only: {unit}"
+                )));
+            }
+            let built_in = ParseConfig {
+                day_minutes: Some(1),
+                ..ParseConfig::default()
+            };
+            if built_in.unit_minutes(&unit).is_some() {
+                return Err(serde::de::Error::custom(format!(
+                    "unit is built in: {unit}"
+                )));
+            }
+            match parse_duration(&length, &ParseConfig::default()) {
+                Ok(Some(minutes)) if minutes > 0 => Ok((unit, minutes)),
+                _ => Err(serde::de::Error::custom(format!(
+                    "invalid duration: {length}"
+                ))),
+            }
+        })
+        .collect()
+}
+
+fn deserialize_suppressions<'de, D>(deserializer: D) -> Result<Vec<Suppression>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let suppressions = Vec::<Suppression>::deserialize(deserializer)?;
+    if suppressions
+        .iter()
+        .any(|suppression| suppression.code.is_none() && suppression.file.is_none())
+    {
+        return Err(serde::de::Error::custom(
+            "a suppression needs a code, a file or both",
+        ));
+    }
+    Ok(suppressions)
+}
@@ -0,0 +1,211 @@
+//! Annotations on a `# TT` header about the day itself, e.g. `# TT 2025-01-15 [holiday]`
+//! or `# TT 2025-01-15 {location:home}`.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+
+use super::ParseError;
+
+/// Leave taken on a day, from a `[holiday]`, `[sick]` or `[half-day]` annotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Leave {
+    Holiday,
+    Sick,
+    HalfDay,
+}
+
+impl Leave {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "holiday" => Some(Leave::Holiday),
+            "sick" => Some(Leave::Sick),
+            "half-day" => Some(Leave::HalfDay),
+            _ => None,
+        }
+    }
+
+    /// Whether the whole day is off, i.e. a holiday or sick day.
+    #[must_use]
+    pub fn is_full_day(self) -> bool {
+        matches!(self, Leave::Holiday | Leave::Sick)
+    }
+
+    /// Part of the day that is not worked.
+    fn fraction(self) -> f64 {
+        match self {
+            Leave::Holiday | Leave::Sick => 1.0,
+            Leave::HalfDay => 0.5,
+        }
+    }
+}
+
+/// What a `# TT` header says about its day besides the date.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DayRecord {
+    pub leave: Option<Leave>,
+    /// `{key:value}` annotations, e.g. `location` for `{location:home}`, which entries
+    /// of the day inherit unless they set the key themselves.
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl DayRecord {
+    /// Reads the annotations among the words after the date, given with their byte offsets
+    /// in the header; other words are ignored.
+    ///
+    /// Unknown `[...]` annotations are returned as warnings.
+    pub(crate) fn parse<'a>(
+        words: impl IntoIterator<Item = (usize, &'a str)>,
+    ) -> (Self, Vec<ParseError>) {
+        let mut record = DayRecord::default();
+        let mut warnings = Vec::new();
+        for (start, word) in words {
+            if let Some(name) = word.strip_prefix('[').and_then(|w| w.strip_suffix(']')) {
+                match Leave::parse(name) {
+                    Some(leave) => record.leave = Some(leave),
+                    None => warnings.push(
+                        ParseError::UnknownDayAnnotation(word.to_string())
+                            .in_token(start..start + word.len()),
+                    ),
+                }
+            } else if let Some((key, value)) = word
+                .strip_prefix('{')
+                .and_then(|w| w.strip_suffix('}'))
+                .and_then(|w| w.split_once(':'))
+                .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+            {
+                record
+                    .metadata
+                    .insert(key.to_lowercase(), value.to_string());
+            }
+        }
+        (record, warnings)
+    }
+
+    /// Whether the header has no annotations.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.leave.is_none() && self.metadata.is_empty()
+    }
+
+    /// Adds the annotations of another header for the same day, the other's taking precedence.
+    pub(crate) fn merge(&mut self, other: &DayRecord) {
+        self.leave = other.leave.or(self.leave);
+        self.metadata
+            .extend(other.metadata.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+}
+
+/// Days tracked in a period and the leave taken on them.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WorkingDays {
+    /// Days with entries or leave.
+    pub days: u32,
+    pub holidays: u32,
+    pub sick_days: u32,
+    pub half_days: u32,
+}
+
+impl WorkingDays {
+    /// Counts the days with entries together with the days of leave, which may have none.
+    #[must_use]
+    pub fn count<'a>(
+        dates_with_entries: impl IntoIterator<Item = &'a NaiveDate>,
+        records: &HashMap<NaiveDate, DayRecord>,
+    ) -> Self {
+        let mut dates: Vec<&NaiveDate> = dates_with_entries.into_iter().collect();
+        dates.extend(
+            records
+                .iter()
+                .filter(|(_, record)| record.leave.is_some())
+                .map(|(date, _)| date),
+        );
+        dates.sort();
+        dates.dedup();
+
+        let mut working_days = WorkingDays {
+            days: u32::try_from(dates.len()).unwrap_or(u32::MAX),
+            ..WorkingDays::default()
+        };
+        for leave in dates
+            .iter()
+            .filter_map(|date| records.get(date).and_then(|record| record.leave))
+        {
+            match leave {
+                Leave::Holiday => working_days.holidays += 1,
+                Leave::Sick => working_days.sick_days += 1,
+                Leave::HalfDay => working_days.half_days += 1,
+            }
+        }
+        working_days
+    }
+
+    /// Whether any leave was taken.
+    #[must_use]
+    pub fn has_leave(&self) -> bool {
+        self.holidays + self.sick_days + self.half_days > 0
+    }
+
+    /// Days worked, a half day counting as 0.5.
+    #[must_use]
+    pub fn worked(&self) -> f64 {
+        f64::from(self.days)
+            - f64::from(self.holidays) * Leave::Holiday.fraction()
+            - f64::from(self.sick_days) * Leave::Sick.fraction()
+            - f64::from(self.half_days) * Leave::HalfDay.fraction()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::words;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, day).unwrap()
+    }
+
+    fn leave(leave: Leave) -> DayRecord {
+        DayRecord {
+            leave: Some(leave),
+            ..DayRecord::default()
+        }
+    }
+
+    #[test]
+    fn parse_should_read_leave_and_metadata() {
+        let (record, warnings) = DayRecord::parse(words("Wed [Holiday] {location:home}"));
+
+        assert_eq!(record.leave, Some(Leave::Holiday));
+        assert_eq!(
+            record.metadata.get("location").map(String::as_str),
+            Some("home")
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn parse_should_warn_about_unknown_annotation() {
+        let (record, warnings) = DayRecord::parse(words("Wed [vacay]"));
+
+        assert!(record.is_empty());
+        assert_eq!(
+            warnings,
+            vec![ParseError::UnknownDayAnnotation("[vacay]".to_string()).in_token(4..11)]
+        );
+    }
+
+    #[test]
+    fn working_days_should_count_half_days_as_half() {
+        let records = HashMap::from([
+            (date(14), leave(Leave::Holiday)),
+            (date(15), leave(Leave::HalfDay)),
+            (date(17), leave(Leave::Sick)),
+        ]);
+
+        let working_days = WorkingDays::count(&[date(13), date(15), date(16)], &records);
+
+        assert_eq!(working_days.days, 5);
+        assert!((working_days.worked() - 2.5).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,322 @@
+//! Parse problems as diagnostics with a stable code, a severity and a suggested fix,
+//! e.g. `2025/01.md:12:1: error[TT001]: missing time: - #prj-1 review`.
+
+use serde::Serialize;
+use std::ops::Range;
+
+use super::{Location, ParseError};
+
+/// How diagnostics are written: as text for people or as JSON lines for tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiagnosticFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl DiagnosticFormat {
+    #[must_use]
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "json" => DiagnosticFormat::Json,
+            _ => DiagnosticFormat::Text,
+        }
+    }
+
+    /// The diagnostic as one line of JSON, or as text with its fix on the next line.
+    #[must_use]
+    pub fn format(self, diagnostic: &Diagnostic) -> String {
+        match self {
+            DiagnosticFormat::Text => diagnostic.to_string(),
+            DiagnosticFormat::Json => serde_json::to_string(diagnostic)
+                .expect("Serializing a diagnostic should never fail"),
+        }
+    }
+}
+
+/// How serious a problem is: errors lose time, warnings keep the entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Where the offending token is in its line, e.g. the `1h30` of `- #prj-1 1h30 review`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    /// Byte offsets of the token in the line.
+    pub start: usize,
+    pub end: usize,
+    /// One-based columns of the token's first character and of the character after it.
+    pub column: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    /// The token at the given byte range of a line whose trimmed content starts `offset`
+    /// bytes in; the whole content when no single token is to blame.
+    pub(crate) fn of(token: Option<Range<usize>>, content: &str, offset: usize) -> Self {
+        let Range { start, end } = token
+            .filter(|token| content.get(token.clone()).is_some())
+            .unwrap_or(0..content.len());
+        let column = |index: usize| offset + content[..index].chars().count() + 1;
+        Span {
+            start: offset + start,
+            end: offset + end,
+            column: column(start),
+            end_column: column(end),
+        }
+    }
+}
+
+/// A parse problem found in a file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    /// Stable code, e.g. `TT001`, that does not change when the message does.
+    pub code: &'static str,
+    pub severity: Severity,
+    #[serde(flatten)]
+    pub location: Option<Location>,
+    #[serde(flatten)]
+    pub span: Option<Span>,
+    pub message: String,
+    pub fix: Option<&'static str>,
+}
+
+impl Diagnostic {
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// One-based column where the problem starts, the start of the line if unknown.
+    #[must_use]
+    pub fn column(&self) -> usize {
+        self.span.map_or(1, |span| span.column)
+    }
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(error: &ParseError) -> Self {
+        let (error, location, span) = match error {
+            ParseError::Located {
+                error,
+                location,
+                span,
+            } => (error.as_ref(), Some(location.clone()), *span),
+            error => (error, None, None),
+        };
+        let (code, severity, fix) = describe(error);
+        Diagnostic {
+            code,
+            severity,
+            location,
+            span,
+            message: error.to_string(),
+            fix,
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(location) = &self.location {
+            write!(f, "{location}:{}: ", self.column())?;
+        }
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)?;
+        if let Some(fix) = self.fix {
+            write!(f, "\n  fix: {fix}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The code, severity and fix of an error; codes are never reused or renumbered.
+fn describe(error: &ParseError) -> (&'static str, Severity, Option<&'static str>) {
+    use Severity::{Error, Warning};
+    match error {
+        ParseError::MissingTime(_) => (
+            "TT001",
+            Error,
+            Some("add a duration such as `30m` or a time range such as `09:00-10:30`"),
+        ),
+        ParseError::InvalidTime(_) => (
+            "TT002",
+            Error,
+            Some("write durations such as `1h30m`, `45m` or `2p`"),
+        ),
+        ParseError::MissingProject(_) => ("TT003", Error, Some("add a tag such as `#prj-name`")),
+        ParseError::MultipleOutcomes(_) => (
+            "TT004",
+            Error,
+            Some("keep a single `##outcome` or use weights such as `##a:60% ##b:40%`"),
+        ),
+        ParseError::InvalidWeights(_) => (
+            "TT005",
+            Error,
+            Some("use percentages adding up to 100% or shares for all weights"),
+        ),
+        ParseError::MultipleTimeRanges(_) => (
+            "TT006",
+            Error,
+            Some("split the entry into one line per time range"),
+        ),
+        ParseError::OverlappingTimeRanges(_) => (
+            "TT007",
+            Warning,
+            Some("correct the start or end of one of the ranges"),
+        ),
+        ParseError::MultipleDurations(_) => (
+            "TT008",
+            Warning,
+            Some("write the time once, or set the `time-marker` to `first` or `sigil`"),
+        ),
+        ParseError::SubtasksExceedTime(_) => (
+            "TT009",
+            Warning,
+            Some("raise the entry's time or lower the subtasks' times"),
+        ),
+        ParseError::InvalidDate(_) => (
+            "TT010",
+            Error,
+            Some("write the date as configured, e.g. `2025-01-15`"),
+        ),
+        ParseError::WeekdayMismatch(_) => {
+            ("TT011", Warning, Some("correct the weekday or the date"))
+        }
+        ParseError::UnknownDayAnnotation(_) => (
+            "TT012",
+            Warning,
+            Some("use `[holiday]`, `[sick]` or `[half-day]`"),
+        ),
+        ParseError::InvalidDirective(_) => ("TT013", Error, None),
+        ParseError::InvalidLineFormat(_) => ("TT014", Error, None),
+        ParseError::ErrorReading(_) => ("TT015", Error, None),
+        ParseError::InvalidPeriod(_) => ("TT016", Error, None),
+        ParseError::InvalidConfig(_) => ("TT017", Error, None),
+        ParseError::EntryOutsideSection(_) => (
+            "TT019",
+            Warning,
+            Some("move the entry below a `## TT YYYY-MM-DD` header"),
+        ),
+        ParseError::DayTooLong(_) => (
+            "TT020",
+            Warning,
+            Some("check the day's entries for a typo such as `10h` for `1h`"),
+        ),
+        ParseError::EntryTooLong(_) => (
+            "TT021",
+            Warning,
+            Some("check the duration or split the entry over the days it took"),
+        ),
+        ParseError::DuplicateEntry(_) => (
+            "TT022",
+            Warning,
+            Some("remove the copy or tell the entries apart in their descriptions"),
+        ),
+        ParseError::WeekendWork(_) => ("TT023", Warning, Some("check the date of the header")),
+        ParseError::FutureDate(_) => ("TT024", Warning, Some("check the date of the header")),
+        ParseError::PartlyWeightedTags(_) => (
+            "TT025",
+            Warning,
+            Some("weight every tag, e.g. `#a*1 #b*1`, or none; unweighted tags get no time"),
+        ),
+        ParseError::InvalidEstimate(_) => (
+            "TT026",
+            Warning,
+            Some("write the estimate as a duration, e.g. `est:3h`"),
+        ),
+        ParseError::StrictProblems(_) => (
+            "TT018",
+            Error,
+            Some("fix the problems or suppress them in the configuration"),
+        ),
+        ParseError::Located { error, .. } | ParseError::InToken { error, .. } => describe(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn located_error_should_become_diagnostic_at_its_location() {
+        let error = ParseError::Located {
+            error: Box::new(ParseError::MissingTime("- #prj-1 review".to_string())),
+            location: Location {
+                file: "2025/01.md".to_string(),
+                line: 12,
+            },
+            span: None,
+        };
+
+        let diagnostic = Diagnostic::from(&error);
+
+        assert_eq!(diagnostic.code, "TT001");
+        assert!(diagnostic.is_error());
+        assert_eq!(
+            diagnostic.to_string().lines().next(),
+            Some("2025/01.md:12:1: error[TT001]: missing time: - #prj-1 review")
+        );
+    }
+
+    #[test]
+    fn kept_entries_should_only_be_warned_about() {
+        let error = ParseError::MultipleDurations("- #prj-1 1h 1h".to_string());
+
+        assert_eq!(Diagnostic::from(&error).severity, Severity::Warning);
+    }
+
+    #[rstest]
+    #[case(Some(9..13), "- #prj-1 1h30 review", 10, 14)]
+    #[case(None, "- #prj-1 review", 1, 16)]
+    #[case(Some(9..30), "- #prj-1 review", 1, 16)]
+    fn span_should_cover_offending_token(
+        #[case] token: Option<Range<usize>>,
+        #[case] content: &str,
+        #[case] column: usize,
+        #[case] end_column: usize,
+    ) {
+        let span = Span::of(token, content, 0);
+
+        assert_eq!((span.column, span.end_column), (column, end_column));
+    }
+
+    #[test]
+    fn span_should_count_indentation_and_characters() {
+        let span = Span::of(Some(15..17), "- #prj-1 café 1x", 2);
+
+        assert_eq!((span.start, span.end), (17, 19));
+        assert_eq!(span.column, 17);
+    }
+
+    #[test]
+    fn json_format_should_write_one_line_with_location_and_span() {
+        let error = ParseError::Located {
+            error: Box::new(ParseError::InvalidTime("1h30".to_string())),
+            location: Location {
+                file: "test.md".to_string(),
+                line: 2,
+            },
+            span: Some(Span::of(Some(9..13), "- #prj-1 1h30", 0)),
+        };
+
+        let json = DiagnosticFormat::Json.format(&Diagnostic::from(&error));
+
+        assert_eq!(
+            json,
+            r#"{"code":"TT002","severity":"error","file":"test.md","line":2,"start":9,"end":13,"column":10,"end_column":14,"message":"invalid time format: 1h30","fix":"write durations such as `1h30m`, `45m` or `2p`"}"#
+        );
+    }
+}
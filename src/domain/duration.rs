@@ -0,0 +1,226 @@
+//! Duration tokens such as `30m`, `1.5h`, `1h30m`, `90min` or `2d`.
+
+use super::config::ParseConfig;
+use super::ParseError;
+
+/// Parses a duration token into minutes.
+///
+/// Returns `Ok(None)` when the token is not a duration, e.g. a plain word, a number without
+/// unit or a number with an unknown unit (`2x`, `3rd`). Tokens built from known units that
+/// cannot be read unambiguously are rejected rather than treated as description.
+///
+/// # Errors
+///
+/// Returns `ParseError::InvalidTime` when the token:
+/// - repeats a unit or lists units from small to large (`30m1h`)
+/// - ends with a number without unit (`1h30`)
+/// - combines a decimal with other units (`1.5h30m`)
+/// - does not come down to whole minutes (`0.5m`)
+/// - contains a malformed number (`1..5h`) or is too large
+pub(crate) fn parse_duration(input: &str, config: &ParseConfig) -> Result<Option<u32>, ParseError> {
+    if !input.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return Ok(None);
+    }
+    let Some(tokens) = tokenize(input) else {
+        return Ok(None);
+    };
+    let Some(segments) = resolve_units(&tokens, config) else {
+        return Ok(None);
+    };
+
+    let invalid = || ParseError::InvalidTime(input.to_string());
+
+    if tokens.dangling_number.is_some() {
+        return Err(invalid());
+    }
+    let is_compound = segments.len() > 1;
+    let units_descending = segments
+        .windows(2)
+        .all(|pair| pair[0].unit_minutes > pair[1].unit_minutes);
+    if !units_descending {
+        return Err(invalid());
+    }
+
+    segments
+        .iter()
+        .try_fold(0_u32, |total, segment| {
+            if is_compound && segment.number.contains('.') {
+                return Err(invalid());
+            }
+            let minutes = segment_minutes(segment).ok_or_else(invalid)?;
+            total.checked_add(minutes).ok_or_else(invalid)
+        })
+        .map(Some)
+}
+
+struct Tokens<'a> {
+    segments: Vec<(&'a str, &'a str)>,
+    dangling_number: Option<&'a str>,
+}
+
+/// Splits `1h30m` into `[("1", "h"), ("30", "m")]`.
+///
+/// Returns `None` for characters that never occur in durations (e.g. `-` in `2020-01-01`)
+/// and for plain numbers without any unit.
+fn tokenize(input: &str) -> Option<Tokens<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let (number, after_number) = rest.split_at(number_end);
+        let unit_end = after_number
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(after_number.len());
+        let (unit, after_unit) = after_number.split_at(unit_end);
+
+        if number.is_empty() {
+            return None;
+        }
+        if unit.is_empty() {
+            if !after_unit.is_empty() || segments.is_empty() {
+                return None;
+            }
+            return Some(Tokens {
+                segments,
+                dangling_number: Some(number),
+            });
+        }
+        segments.push((number, unit));
+        rest = after_unit;
+    }
+
+    Some(Tokens {
+        segments,
+        dangling_number: None,
+    })
+}
+
+struct Segment<'a> {
+    number: &'a str,
+    unit_minutes: u32,
+}
+
+/// Looks up every unit; a single unknown unit means the token is not a duration.
+fn resolve_units<'a>(tokens: &Tokens<'a>, config: &ParseConfig) -> Option<Vec<Segment<'a>>> {
+    tokens
+        .segments
+        .iter()
+        .map(|(number, unit)| {
+            config.unit_minutes(unit).map(|unit_minutes| Segment {
+                number,
+                unit_minutes,
+            })
+        })
+        .collect()
+}
+
+fn segment_minutes(segment: &Segment) -> Option<u32> {
+    let (whole, fraction) = segment
+        .number
+        .split_once('.')
+        .unwrap_or((segment.number, ""));
+    if whole.is_empty() {
+        return None;
+    }
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if !is_digits(whole) || !is_digits(fraction) || segment.number.ends_with('.') {
+        return None;
+    }
+
+    let digits = format!("{whole}{fraction}");
+    let scaled_value = digits.parse::<u64>().ok()?;
+    let scale = 10_u64.checked_pow(u32::try_from(fraction.len()).ok()?)?;
+    let scaled_minutes = scaled_value.checked_mul(u64::from(segment.unit_minutes))?;
+
+    if scaled_minutes % scale != 0 {
+        return None;
+    }
+    u32::try_from(scaled_minutes / scale).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn minutes_of(input: &str) -> Result<Option<u32>, ParseError> {
+        parse_duration(input, &ParseConfig::default())
+    }
+
+    #[rstest]
+    #[case("45m", 45)]
+    #[case("2h", 120)]
+    #[case("2p", 60)]
+    #[case("1.5h", 90)]
+    #[case("0.25h", 15)]
+    #[case("1h30m", 90)]
+    #[case("2h15m", 135)]
+    #[case("90min", 90)]
+    #[case("30mins", 30)]
+    #[case("45minutes", 45)]
+    #[case("2hrs", 120)]
+    #[case("1hour", 60)]
+    fn duration_should_be_read_in_minutes(#[case] input: &str, #[case] expected: u32) {
+        assert_eq!(minutes_of(input), Ok(Some(expected)));
+    }
+
+    #[rstest]
+    fn duration_should_not_be_recognised_when_not_a_duration(
+        #[values("task", "2x", "3rd", "1.5", "42", "2020-01-01", "v2h", "h")] input: &str,
+    ) {
+        assert_eq!(minutes_of(input), Ok(None));
+    }
+
+    #[rstest]
+    fn duration_should_be_invalid_when_ambiguous(
+        #[values(
+            "1h30",
+            "30m1h",
+            "1h1h",
+            "1.5h30m",
+            "0.5m",
+            "1..5h",
+            "1.h",
+            ".5h",
+            "100000000000000000000h"
+        )]
+        input: &str,
+    ) {
+        assert_eq!(
+            minutes_of(input),
+            Err(ParseError::InvalidTime(input.to_string()))
+        );
+    }
+
+    #[test]
+    fn duration_should_use_configured_day_length() {
+        let config = ParseConfig {
+            day_minutes: Some(450),
+            ..ParseConfig::default()
+        };
+
+        assert_eq!(parse_duration("2d", &config), Ok(Some(900)));
+        assert_eq!(parse_duration("1d2h", &config), Ok(Some(570)));
+        assert_eq!(parse_duration("0.5d", &config), Ok(Some(225)));
+    }
+
+    #[test]
+    fn duration_should_not_read_days_without_day_length() {
+        assert_eq!(minutes_of("3d"), Ok(None));
+    }
+    #[test]
+    fn duration_should_use_configured_pomodoro_length_and_units() {
+        let config = ParseConfig {
+            pomodoro_minutes: 25,
+            units: [("block".to_string(), 45)].into(),
+            ..ParseConfig::default()
+        };
+
+        assert_eq!(parse_duration("2p", &config), Ok(Some(50)));
+        assert_eq!(parse_duration("1h2block", &config), Ok(Some(150)));
+        assert_eq!(parse_duration("2blocks", &config), Ok(None));
+    }
+}
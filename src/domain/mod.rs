@@ -1,17 +1,35 @@
+pub mod config;
 pub mod dates;
+pub mod days;
+pub mod diagnostics;
+mod duration;
 pub mod reporting;
+pub mod rules;
 pub mod tags;
+pub mod tickets;
 pub mod time;
-use std::collections::VecDeque;
+mod weights;
+use std::collections::{BTreeMap, VecDeque};
+use std::ops::Range;
 
+use chrono::NaiveDate;
+
+use config::{ParseConfig, TimeMarker};
 pub use dates::range::{DateRange, PeriodRequested};
+use days::DayRecord;
 pub use reporting::{PeriodDescription, TimeTrackingResult, TrackedTime, TrackingPeriod};
 use serde::Serialize;
 use tags::Tag;
+use time::TimeRange;
+use weights::Weight;
 
 #[derive(Debug, PartialEq)]
 pub enum EntryLineParseResult {
-    Entry(TimeEntry),
+    /// A valid entry, with warnings about parts of the line that look suspicious.
+    Entry {
+        entry: Box<TimeEntry>,
+        warnings: Vec<ParseError>,
+    },
     NotAnEntry,
     Malformed(ParseError),
 }
@@ -22,19 +40,197 @@ pub struct TimeEntry {
     pub minutes: u32,
     pub description: Option<String>,
     pub outcome: Option<Outcome>,
+    pub time_range: Option<TimeRange>,
+    /// Set when the entry is a task checkbox.
+    pub status: Option<TaskStatus>,
+    /// `key:value` attributes, e.g. `client:acme billable:no`.
+    pub metadata: BTreeMap<String, String>,
+    /// People mentioned with `@name`, e.g. the other side of a pairing session.
+    pub people: Vec<String>,
+    /// Planned time in minutes, from an `est:3h` annotation.
+    pub estimate: Option<u32>,
+    /// Ticket keys found in the description, e.g. `PROJ-1234`.
+    pub tickets: Vec<String>,
+    /// Day the entry was tracked on; `None` for a line parsed on its own.
+    pub date: Option<NaiveDate>,
+    /// File and line the entry was read from; `None` for a line parsed on its own.
+    pub source: Option<Location>,
+    /// Shares of the time for weighted tags, e.g. `#a*2 #b*1`; empty when the time is not split.
+    tag_shares: Vec<(Tag, u32)>,
+    /// Shares of the time for weighted outcomes, e.g. `##launch:70% ##hiring:30%`.
+    outcome_shares: Vec<(Outcome, Weight)>,
+    subtasks: Vec<TimeEntry>,
+}
+
+/// An entry line read before its subtasks are known.
+#[derive(Debug)]
+pub(crate) struct ParsedEntry {
+    pub(crate) entry: TimeEntry,
+    pub(crate) warnings: Vec<ParseError>,
+    /// Whether the line itself carries time; an entry without time takes the sum of its subtasks.
+    pub(crate) has_time: bool,
+    /// Byte range of the entry's clock range in the line, blamed when it overlaps another.
+    pub(crate) range_token: Option<Range<usize>>,
+}
+
+/// What is required of an entry line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryRole {
+    /// A `- #tag` line that must carry its own time.
+    Standalone,
+    /// A `- #tag` line whose time may come from its subtasks.
+    Parent,
+    /// An indented `- ` line below an entry; tags and time are optional.
+    Subtask,
+    /// A `- ` line below a sub-heading that provides tags, e.g. `### #prj-alpha`;
+    /// its own tags are optional and its time may come from its subtasks.
+    Scoped,
 }
 
 impl TimeEntry {
     #[must_use]
     pub fn parse(line: &str) -> EntryLineParseResult {
-        EntryLine::parse(line)
-            .map(|entry_line| parse_line(&entry_line))
+        Self::parse_with_config(line, &ParseConfig::default())
+    }
+
+    #[must_use]
+    pub fn parse_with_config(line: &str, config: &ParseConfig) -> EntryLineParseResult {
+        EntryLine::parse(line, EntryRole::Standalone, config)
+            .map(|entry_line| parse_line(&entry_line, config, EntryRole::Standalone))
             .map_or(EntryLineParseResult::NotAnEntry, |result| match result {
-                Ok(entry) => EntryLineParseResult::Entry(entry),
+                Ok(ParsedEntry {
+                    entry, warnings, ..
+                }) => EntryLineParseResult::Entry {
+                    entry: Box::new(entry),
+                    warnings,
+                },
                 Err(err) => EntryLineParseResult::Malformed(err),
             })
     }
 
+    /// Parses an entry line that may be followed by subtasks, so missing time is not an error yet.
+    pub(crate) fn parse_parent(
+        line: &str,
+        config: &ParseConfig,
+    ) -> Option<Result<ParsedEntry, ParseError>> {
+        EntryLine::parse(line, EntryRole::Parent, config)
+            .map(|entry_line| parse_line(&entry_line, config, EntryRole::Parent))
+    }
+
+    /// Parses an entry line below a sub-heading whose tags it inherits, e.g. `- 1h design review`.
+    ///
+    /// A line with neither time nor a tag of its own is a note, e.g. `- remember to ping Bob`.
+    pub(crate) fn parse_scoped(
+        line: &str,
+        config: &ParseConfig,
+    ) -> Option<Result<ParsedEntry, ParseError>> {
+        let entry_line = EntryLine::parse(line, EntryRole::Scoped, config)?;
+        match parse_line(&entry_line, config, EntryRole::Scoped) {
+            Ok(parsed) if !parsed.has_time && parsed.entry.tags.is_empty() => None,
+            result => Some(result),
+        }
+    }
+
+    /// Parses an indented `- ` line below an entry, e.g. `- 30m write tests`.
+    pub(crate) fn parse_subtask(
+        line: &str,
+        config: &ParseConfig,
+    ) -> Option<Result<ParsedEntry, ParseError>> {
+        EntryLine::parse(line, EntryRole::Subtask, config)
+            .map(|entry_line| parse_line(&entry_line, config, EntryRole::Subtask))
+    }
+
+    /// Attaches the subtasks parsed below this entry.
+    ///
+    /// Subtasks inherit the entry's tags, outcome, checkbox status, metadata, people and tickets.
+    /// An entry without time of its own takes the sum of its subtasks; otherwise subtasks
+    /// are part of the entry's time and exceeding it is reported as a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::MissingTime` if neither the entry nor its subtasks carry time.
+    pub(crate) fn with_subtasks(
+        mut self,
+        subtasks: Vec<TimeEntry>,
+        has_time: bool,
+        line: &str,
+    ) -> Result<(Self, Option<ParseError>), ParseError> {
+        let subtasks: Vec<TimeEntry> = subtasks
+            .into_iter()
+            .map(|subtask| self.inherited_by(subtask))
+            .collect();
+        let subtask_minutes: u32 = subtasks.iter().map(|subtask| subtask.minutes).sum();
+
+        let warning = if has_time {
+            (subtask_minutes > self.minutes)
+                .then(|| ParseError::SubtasksExceedTime(line.to_string()))
+        } else if subtask_minutes > 0 {
+            self.minutes = subtask_minutes;
+            None
+        } else {
+            return Err(ParseError::MissingTime(line.to_string()));
+        };
+
+        self.subtasks = subtasks;
+        Ok((self, warning))
+    }
+
+    fn inherited_by(&self, subtask: TimeEntry) -> TimeEntry {
+        let mut subtask = subtask.with_scope(&self.tags, self.outcome.as_ref());
+        subtask.status = subtask.status.or(self.status);
+        for (key, value) in &self.metadata {
+            subtask
+                .metadata
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        for person in &self.people {
+            if !subtask.people.contains(person) {
+                subtask.people.push(person.clone());
+            }
+        }
+        for ticket in &self.tickets {
+            if !subtask.tickets.contains(ticket) {
+                subtask.tickets.push(ticket.clone());
+            }
+        }
+        subtask
+    }
+
+    /// Adds the tags and outcome of an enclosing scope, e.g. a parent entry or a sub-heading.
+    ///
+    /// The scope's tags come first, so its first tag becomes the main context;
+    /// an outcome of the entry itself takes precedence.
+    pub(crate) fn with_scope(mut self, tags: &[Tag], outcome: Option<&Outcome>) -> Self {
+        let own_tags = std::mem::replace(&mut self.tags, tags.to_vec());
+        for tag in own_tags {
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
+        }
+        if self.outcome.is_none() {
+            self.outcome = outcome.cloned();
+        }
+        self
+    }
+
+    /// Records the day and the file and line the entry was read from.
+    pub(crate) fn written_at(mut self, date: NaiveDate, source: Location) -> Self {
+        self.date = Some(date);
+        self.source = Some(source);
+        self
+    }
+
+    /// Adds the `{key:value}` annotations of the entry's day, keeping its own values.
+    pub(crate) fn with_day(mut self, day: &DayRecord) -> Self {
+        for (key, value) in &day.metadata {
+            self.metadata
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        self
+    }
+
     /// Returns the main context (first tag) of this time entry.
     ///
     /// This method is guaranteed to succeed for valid entries since the parser
@@ -53,10 +249,134 @@ impl TimeEntry {
             .to_string()
     }
 
+    /// Minutes per tag the entry's time counts for: split over its weighted tags,
+    /// or else all of it for the main context.
+    #[must_use]
+    pub fn minutes_per_context(&self) -> Vec<(String, u32)> {
+        if self.tag_shares.is_empty() {
+            return vec![(self.main_context(), self.minutes)];
+        }
+        let shares: Vec<u32> = self.tag_shares.iter().map(|(_, share)| *share).collect();
+        self.tag_shares
+            .iter()
+            .zip(weights::apportion(self.minutes, &shares))
+            .map(|((tag, _), minutes)| (tag.raw_value(), minutes))
+            .collect()
+    }
+
+    /// Minutes of the entry's time that count for the tag or a tag below it: the tag's part
+    /// when the time is split over weighted tags, else all of it.
+    #[must_use]
+    pub fn minutes_for(&self, tag: &Tag) -> u32 {
+        self.part_for(self.minutes, tag)
+    }
+
+    /// The entry as far as it counts for the tag, its subtasks' time split like its own.
+    pub(crate) fn counted_for(&self, tag: &Tag) -> TimeEntry {
+        let mut entry = self.clone();
+        entry.minutes = self.part_for(self.minutes, tag);
+        for subtask in &mut entry.subtasks {
+            subtask.minutes = self.part_for(subtask.minutes, tag);
+        }
+        entry
+    }
+
+    /// The part of the minutes the weighted tags within the tag get.
+    fn part_for(&self, minutes: u32, tag: &Tag) -> u32 {
+        if self.tag_shares.is_empty() {
+            return minutes;
+        }
+        let shares: Vec<u32> = self.tag_shares.iter().map(|(_, share)| *share).collect();
+        self.tag_shares
+            .iter()
+            .zip(weights::apportion(minutes, &shares))
+            .filter(|((share_tag, _), _)| share_tag.is_within(tag))
+            .map(|(_, minutes)| minutes)
+            .sum()
+    }
+
+    /// Minutes per outcome the entry's time counts for: split over its weighted outcomes,
+    /// or else all of it for its outcome, if any.
+    #[must_use]
+    pub fn minutes_per_outcome(&self) -> Vec<(&str, u32)> {
+        if self.outcome_shares.is_empty() {
+            return self
+                .outcome
+                .iter()
+                .map(|outcome| (outcome.description(), self.minutes))
+                .collect();
+        }
+        let shares: Vec<u32> = self
+            .outcome_shares
+            .iter()
+            .map(|(_, weight)| weight.value())
+            .collect();
+        self.outcome_shares
+            .iter()
+            .zip(weights::apportion(self.minutes, &shares))
+            .map(|((outcome, _), minutes)| (outcome.description(), minutes))
+            .collect()
+    }
+
+    /// Outcomes as written, with their weights when the time is split over several,
+    /// e.g. `launch:70%` and `hiring:30%`.
+    #[must_use]
+    pub fn outcomes_as_written(&self) -> Vec<String> {
+        if self.outcome_shares.is_empty() {
+            return self
+                .outcome
+                .iter()
+                .map(|outcome| outcome.description().to_string())
+                .collect();
+        }
+        self.outcome_shares
+            .iter()
+            .map(|(outcome, weight)| format!("{}{weight}", outcome.description()))
+            .collect()
+    }
+
     #[must_use]
     pub fn get_tags(&self) -> &[Tag] {
         &self.tags
     }
+
+    /// The indented subtasks listed below this entry.
+    #[must_use]
+    pub fn subtasks(&self) -> &[TimeEntry] {
+        &self.subtasks
+    }
+}
+
+/// Whether an entry written as a task checkbox, e.g. `- [x] #dev 1h`, is completed.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatus {
+    Open,
+    Done,
+}
+
+impl TaskStatus {
+    /// Splits a leading `[ ]`, `[x]` or `[X]` checkbox off the line.
+    fn strip_checkbox(line: &str) -> (Option<TaskStatus>, &str) {
+        if let Some(rest) = line.strip_prefix("[ ] ") {
+            (Some(TaskStatus::Open), rest)
+        } else if let Some(rest) = line
+            .strip_prefix("[x] ")
+            .or_else(|| line.strip_prefix("[X] "))
+        {
+            (Some(TaskStatus::Done), rest)
+        } else {
+            (None, line)
+        }
+    }
+
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            TaskStatus::Open => "open",
+            TaskStatus::Done => "done",
+        }
+    }
 }
 
 /// Desired overall outcome this project or task is part of.
@@ -75,72 +395,213 @@ impl Outcome {
     }
 }
 
-struct EntryLine<'a>(pub(crate) &'a str);
+struct EntryLine<'a> {
+    line: &'a str,
+    /// The line without its list marker and checkbox.
+    content: &'a str,
+    status: Option<TaskStatus>,
+}
 
 impl EntryLine<'_> {
-    pub(crate) fn parse(line: &str) -> Option<EntryLine<'_>> {
-        if EntryLine::is_line_entry(line) {
-            Some(EntryLine(line))
-        } else {
-            None
-        }
-    }
-
-    fn is_line_entry(line: &str) -> bool {
-        line.starts_with("- #")
+    fn parse<'a>(line: &'a str, role: EntryRole, config: &ParseConfig) -> Option<EntryLine<'a>> {
+        let after_marker = config
+            .entry_markers
+            .iter()
+            .find_map(|marker| marker.strip_from(line))?;
+        let (status, content) = TaskStatus::strip_checkbox(after_marker);
+        let is_entry = match role {
+            EntryRole::Standalone | EntryRole::Parent => content.starts_with('#'),
+            EntryRole::Subtask | EntryRole::Scoped => true,
+        };
+        is_entry.then_some(EntryLine {
+            line,
+            content,
+            status,
+        })
     }
 
     pub(crate) fn get_line(&self) -> &str {
-        self.0
+        self.line
     }
 
-    /// Returns the actual content of the line, without the prefix that identifies the line as an entry line.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the entry line does not start with "- " prefix, which indicates invalid struct state.
+    /// Returns the actual content of the line, without the list marker and checkbox that identify it as an entry line.
     pub(crate) fn entry(&self) -> &str {
-        self.0.strip_prefix("- ").expect("invalid struct state")
+        self.content
     }
+
+    /// Byte offset of the content in the line; only the marker and checkbox are cut off before it.
+    fn content_offset(&self) -> usize {
+        self.line.len() - self.content.len()
+    }
+}
+
+/// Words of a line with their byte offsets, e.g. `(2, "#prj-1")` for `- #prj-1 1h`.
+pub(crate) fn words(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split_whitespace().scan(0, move |searched, word| {
+        let start = *searched + line[*searched..].find(word)?;
+        *searched = start + word.len();
+        Some((start, word))
+    })
 }
 
-fn parse_line(entry_line: &EntryLine) -> Result<TimeEntry, ParseError> {
+fn parse_line(
+    entry_line: &EntryLine,
+    config: &ParseConfig,
+    role: EntryRole,
+) -> Result<ParsedEntry, ParseError> {
     let line_no_prefix = entry_line.entry();
-    let parts = line_no_prefix.split_whitespace();
+    let content_offset = entry_line.content_offset();
+    let token =
+        |start: usize, part: &str| content_offset + start..content_offset + start + part.len();
 
     let mut projects = VecDeque::new();
     let mut minutes = 0;
     let mut description = Vec::new();
     let mut time_found = false;
-    let mut multiple_outcomes_found = false;
-    let mut outcome = None;
-
-    for part in parts {
-        match parse_part(part) {
+    let mut outcomes: Vec<(String, Option<Weight>)> = Vec::new();
+    let mut outcome_tokens = Vec::new();
+    let mut tag_weights: Vec<(Tag, Weight)> = Vec::new();
+    let mut tag_weight_tokens = Vec::new();
+    let mut outcome_weight_tokens = Vec::new();
+    let mut time_range = None;
+    let mut range_token = None;
+    let mut metadata = BTreeMap::new();
+    let mut people: Vec<String> = Vec::new();
+    let mut estimate = None;
+    let mut multiple_time_ranges_found = false;
+    let mut invalid_estimates = Vec::new();
+    // Time tokens only separated by tags, e.g. `1h 30m`, are one duration.
+    let mut durations = 0;
+    let mut words_since_time = false;
+    let mut description_started = false;
+
+    for (start, part) in words(line_no_prefix) {
+        let parsed = match parse_part(part, config) {
+            // Past the first duration, tokens only look like time and stay description.
+            Err(ParseError::InvalidTime(_))
+                if config.time_marker == TimeMarker::First
+                    && durations > 0
+                    && parse_metadata(part, config).is_none() =>
+            {
+                Ok(LinePart::DescriptionPart(part))
+            }
+            parsed => parsed,
+        };
+        let line_part = parsed.map(|line_part| match line_part {
+            LinePart::Time(_) | LinePart::TimeRange(_) => {
+                if durations == 0 || words_since_time {
+                    durations += 1;
+                }
+                words_since_time = false;
+                if config.time_marker.counts_duration(durations) {
+                    line_part
+                } else {
+                    LinePart::DescriptionPart(part)
+                }
+            }
+            LinePart::Tag(..) if config.tags.leading_only && description_started => {
+                LinePart::DescriptionPart(part)
+            }
+            _ => line_part,
+        });
+        match line_part {
             Ok(LinePart::Time(time)) => {
                 minutes += time;
                 time_found = true;
             }
-            Ok(LinePart::Tag(project_found)) => {
+            Ok(LinePart::TimeRange(range)) => {
+                if time_range.is_some() {
+                    multiple_time_ranges_found = true;
+                }
+                minutes += range.minutes();
+                time_found = true;
+                time_range = Some(range);
+                range_token = Some(token(start, part));
+            }
+            Ok(LinePart::Tag(project_found, weight)) => {
+                if let Some(weight) = weight {
+                    tag_weights.push((project_found.clone(), weight));
+                    tag_weight_tokens.push(token(start, part));
+                }
                 projects.push_back(project_found);
             }
-            Ok(LinePart::Outcome(outcome_found)) => {
-                if outcome.is_some() {
-                    multiple_outcomes_found = true;
+            Ok(LinePart::Outcome(outcome_found, weight)) => {
+                if weight.is_some() {
+                    outcome_weight_tokens.push(token(start, part));
+                }
+                outcomes.push((outcome_found, weight));
+                outcome_tokens.push(token(start, part));
+            }
+            Ok(LinePart::Metadata(key, value)) => {
+                metadata.insert(key.to_string(), value.to_string());
+            }
+            Ok(LinePart::Estimate(minutes)) => {
+                estimate = Some(minutes);
+            }
+            Ok(LinePart::InvalidEstimate(written)) => {
+                invalid_estimates.push(
+                    ParseError::InvalidEstimate(written.to_string()).in_token(token(start, part)),
+                );
+                description_started = true;
+                words_since_time = true;
+                description.push(written);
+            }
+            Ok(LinePart::Person(name, mention)) => {
+                if !people.iter().any(|person| person == name) {
+                    people.push(name.to_string());
                 }
-                outcome = Some(Outcome::parse(outcome_found));
+                description_started = true;
+                words_since_time = true;
+                description.push(mention);
             }
-            Ok(LinePart::DescriptionPart(desc)) => description.push(desc),
-            Err(err) => return Err(err),
+            Ok(LinePart::DescriptionPart(desc)) => {
+                description_started = true;
+                words_since_time = true;
+                description.push(desc);
+            }
+            Err(err) => return Err(err.in_token(token(start, part))),
         }
     }
 
-    if !time_found {
+    if !time_found && role == EntryRole::Standalone {
         return Err(ParseError::MissingTime(entry_line.get_line().to_string()));
     }
 
-    if multiple_outcomes_found {
-        return Err(ParseError::MultipleOutcomes(
+    if outcomes.len() > 1 && outcomes.iter().any(|(_, weight)| weight.is_none()) {
+        return Err(
+            ParseError::MultipleOutcomes(entry_line.get_line().to_string())
+                .in_token(outcome_tokens[1].clone()),
+        );
+    }
+    let outcome_weights: Vec<Weight> = outcomes.iter().filter_map(|(_, weight)| *weight).collect();
+    let tag_weight_values: Vec<Weight> = tag_weights.iter().map(|(_, weight)| *weight).collect();
+    let invalid_weights = |weighted: &Range<usize>| {
+        ParseError::InvalidWeights(entry_line.get_line().to_string()).in_token(weighted.clone())
+    };
+    if weights::shares(&outcome_weights).is_none() {
+        return Err(invalid_weights(&outcome_weight_tokens[0]));
+    }
+    let Some(tag_shares) = weights::shares(&tag_weight_values) else {
+        return Err(invalid_weights(&tag_weight_tokens[0]));
+    };
+    let outcome_shares: Vec<(Outcome, Weight)> = outcomes
+        .iter()
+        .filter_map(|(outcome, weight)| {
+            weight.map(|weight| (Outcome::parse(outcome.clone()), weight))
+        })
+        .collect();
+    let tag_shares: Vec<(Tag, u32)> = tag_weights
+        .into_iter()
+        .map(|(tag, _)| tag)
+        .zip(tag_shares)
+        .collect();
+    let outcome = outcomes
+        .into_iter()
+        .next()
+        .map(|(outcome, _)| Outcome::parse(outcome));
+
+    if multiple_time_ranges_found {
+        return Err(ParseError::MultipleTimeRanges(
             entry_line.get_line().to_string(),
         ));
     }
@@ -150,44 +611,154 @@ fn parse_line(entry_line: &EntryLine) -> Result<TimeEntry, ParseError> {
     } else {
         Some(description.join(" "))
     };
+    let tickets = description
+        .as_deref()
+        .zip(config.ticket_pattern.as_ref())
+        .map(|(description, pattern)| pattern.find_in(description))
+        .unwrap_or_default();
     let projects: Vec<Tag> = projects.into();
 
     let tags = projects;
-    if tags.is_empty() || tags[0].raw_value().is_empty() {
+    let tag_required = !matches!(role, EntryRole::Subtask | EntryRole::Scoped) || !tags.is_empty();
+    if tag_required && (tags.is_empty() || tags[0].raw_value().is_empty()) {
         return Err(ParseError::MissingProject(
             entry_line.get_line().to_string(),
         ));
     }
 
-    Ok(TimeEntry {
+    let mut warnings = invalid_estimates;
+    if durations > 1 {
+        warnings.push(ParseError::MultipleDurations(
+            entry_line.get_line().to_string(),
+        ));
+    }
+    if !tag_shares.is_empty() && tag_shares.len() < tags.len() {
+        warnings.push(ParseError::PartlyWeightedTags(
+            entry_line.get_line().to_string(),
+        ));
+    }
+
+    let entry = TimeEntry {
         tags,
         minutes,
         description,
         outcome,
+        time_range,
+        status: entry_line.status,
+        metadata,
+        people,
+        estimate,
+        tickets,
+        date: None,
+        source: None,
+        tag_shares,
+        outcome_shares,
+        subtasks: Vec::new(),
+    };
+    Ok(ParsedEntry {
+        entry,
+        warnings,
+        has_time: time_found,
+        range_token,
     })
 }
 
 enum LinePart<'a> {
     Time(u32),
-    Tag(Tag),
-    Outcome(String),
+    TimeRange(TimeRange),
+    Tag(Tag, Option<Weight>),
+    Outcome(String, Option<Weight>),
+    Metadata(&'a str, &'a str),
+    /// An `est:3h` annotation, in minutes.
+    Estimate(u32),
+    /// An `est:` token that is not a duration, e.g. `est:soon`, which stays in the description.
+    InvalidEstimate(&'a str),
+    /// A `@name` mention, which also stays in the description.
+    Person(&'a str, &'a str),
     DescriptionPart(&'a str),
 }
 
-fn parse_part(part: &str) -> Result<LinePart<'_>, ParseError> {
+fn parse_part<'a>(part: &'a str, config: &ParseConfig) -> Result<LinePart<'a>, ParseError> {
     if part.starts_with("##") {
         let outcome = part
             .strip_prefix("##")
             .expect("outcome should have had '##' prefix");
-        Ok(LinePart::Outcome(outcome.to_string()))
+        let (outcome, weight) = weights::split_weight(outcome);
+        Ok(LinePart::Outcome(outcome.to_string(), weight))
     } else if part.starts_with('#') {
         let raw_tag = part
             .strip_prefix("#")
             .expect("project should have had '#' prefix");
-        let tag = Tag::from_raw(raw_tag);
-        Ok(LinePart::Tag(tag))
+        let (raw_tag, weight) = weights::split_weight(raw_tag);
+        if config.tags.accepts(raw_tag) {
+            Ok(LinePart::Tag(Tag::from_raw(raw_tag), weight))
+        } else {
+            Ok(LinePart::DescriptionPart(part))
+        }
+    } else if let Some(name) = parse_mention(part) {
+        Ok(LinePart::Person(name, part))
+    } else if let Some((key, value)) = parse_metadata(part, config) {
+        if key == ESTIMATE_KEY {
+            Ok(parse_estimate(part, value, config))
+        } else {
+            Ok(LinePart::Metadata(key, value))
+        }
     } else {
-        match parse_time(part)? {
+        match config.time_marker {
+            TimeMarker::Sigil => match part.strip_prefix(TIME_SIGIL) {
+                Some(time) => parse_time_part(time, config).map(|line_part| match line_part {
+                    LinePart::DescriptionPart(_) => LinePart::DescriptionPart(part),
+                    time_part => time_part,
+                }),
+                None => Ok(LinePart::DescriptionPart(part)),
+            },
+            TimeMarker::Any | TimeMarker::First => parse_time_part(part, config),
+        }
+    }
+}
+
+/// Splits a `key:value` token, e.g. `client:acme`, whose key is configured or `est`.
+///
+/// Other words with a colon, such as `TODO:fix`, clock ranges and URLs, stay in the description.
+fn parse_metadata<'a>(part: &'a str, config: &ParseConfig) -> Option<(&'a str, &'a str)> {
+    let (key, value) = part.split_once(':')?;
+    let known_key = key == ESTIMATE_KEY || config.metadata_keys.iter().any(|known| known == key);
+    let valid_value = !value.is_empty() && !value.starts_with('/');
+    (known_key && valid_value).then_some((key, value))
+}
+
+/// Metadata key of the planned time of an entry, e.g. `est:3h`.
+const ESTIMATE_KEY: &str = "est";
+
+fn parse_estimate<'a>(part: &'a str, value: &str, config: &ParseConfig) -> LinePart<'a> {
+    match duration::parse_duration(value, config) {
+        Ok(Some(minutes)) => LinePart::Estimate(minutes),
+        Ok(None) | Err(_) => LinePart::InvalidEstimate(part),
+    }
+}
+
+/// The name in a `@name` mention, without trailing punctuation, e.g. `bob` for `@bob,`.
+///
+/// Names start with a letter, so sigil durations such as `@2h` are not mentions.
+fn parse_mention(part: &str) -> Option<&str> {
+    let name = part
+        .strip_prefix('@')?
+        .trim_end_matches([',', '.', ';', ':', '!', '?', ')']);
+    let valid_name = name.starts_with(|c: char| c.is_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    valid_name.then_some(name)
+}
+
+/// Prefix marking a time token when the sigil time marker is used, e.g. `@2h`.
+const TIME_SIGIL: char = '@';
+
+fn parse_time_part<'a>(part: &'a str, config: &ParseConfig) -> Result<LinePart<'a>, ParseError> {
+    if let Some(range) = TimeRange::parse(part) {
+        range.map(LinePart::TimeRange)
+    } else {
+        match parse_time(part, config)? {
             ParseTimeResult::Time(minutes) => Ok(LinePart::Time(minutes)),
             ParseTimeResult::NotTime(text) => Ok(LinePart::DescriptionPart(text)),
         }
@@ -200,59 +771,144 @@ enum ParseTimeResult<'a> {
     NotTime(&'a str),
 }
 
-fn parse_time(input: &str) -> Result<ParseTimeResult<'_>, ParseError> {
-    let (base, multiplier) = match input.chars().last() {
-        Some('m') => (input.strip_suffix('m').unwrap(), 1),
-        Some('h') => (input.strip_suffix('h').unwrap(), 60),
-        Some('p') => (input.strip_suffix('p').unwrap(), 30),
-        _ => return Ok(ParseTimeResult::NotTime(input)),
-    };
-
-    match base.parse::<u32>() {
-        Ok(val) => Ok(ParseTimeResult::Time(val * multiplier)),
-        Err(e) => match e.kind() {
-            std::num::IntErrorKind::InvalidDigit => Ok(ParseTimeResult::NotTime(input)),
-            _ => Err(ParseError::InvalidTime(input.to_string())),
-        },
+fn parse_time<'a>(input: &'a str, config: &ParseConfig) -> Result<ParseTimeResult<'a>, ParseError> {
+    match duration::parse_duration(input, config)? {
+        Some(minutes) => Ok(ParseTimeResult::Time(minutes)),
+        None => Ok(ParseTimeResult::NotTime(input)),
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize)]
 pub struct Location {
     pub file: String,
     pub line: usize,
 }
 
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum ParseError {
     ErrorReading(String),
     InvalidLineFormat(String),
     InvalidTime(String),
     InvalidDate(String),
+    WeekdayMismatch(String),
+    UnknownDayAnnotation(String),
     MissingTime(String),
     MissingProject(String),
     MultipleOutcomes(String),
+    InvalidWeights(String),
+    /// Some tags of a line are weighted and others not; only the weighted ones get time.
+    PartlyWeightedTags(String),
+    /// An `est:` token that is not a duration; the entry is kept without estimate.
+    InvalidEstimate(String),
+    MultipleTimeRanges(String),
+    OverlappingTimeRanges(String),
+    MultipleDurations(String),
+    SubtasksExceedTime(String),
+    /// A line that reads as an entry but is not below a TT header, so its time is not tracked.
+    EntryOutsideSection(String),
+    InvalidDirective(String),
+    DayTooLong(String),
+    EntryTooLong(String),
+    DuplicateEntry(String),
+    WeekendWork(String),
+    FutureDate(String),
     InvalidPeriod(String),
+    InvalidConfig(String),
+    /// Problems found in strict mode, which fail the run.
+    StrictProblems(usize),
     Located {
         error: Box<ParseError>,
         location: Location,
+        /// The offending token in the line, if known.
+        span: Option<diagnostics::Span>,
+    },
+    /// An error about one token of its line, at the token's byte range in the trimmed line.
+    InToken {
+        error: Box<ParseError>,
+        token: Range<usize>,
     },
 }
 
+impl ParseError {
+    /// This error, blamed on the token at the given byte range of its line.
+    #[must_use]
+    pub(crate) fn in_token(self, token: Range<usize>) -> Self {
+        ParseError::InToken {
+            error: Box::new(self),
+            token,
+        }
+    }
+
+    /// This error without its token, and the byte range of the token if known.
+    pub(crate) fn split_token(self) -> (Self, Option<Range<usize>>) {
+        match self {
+            ParseError::InToken { error, token } => (*error, Some(token)),
+            error => (error, None),
+        }
+    }
+}
+
 impl std::fmt::Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ParseError::InvalidLineFormat(line) => write!(f, "invalid line format: {line}"),
             ParseError::InvalidTime(time) => write!(f, "invalid time format: {time}"),
             ParseError::InvalidDate(date) => write!(f, "invalid date format: {date}"),
+            ParseError::WeekdayMismatch(date) => write!(f, "weekday does not match date: {date}"),
+            ParseError::UnknownDayAnnotation(annotation) => {
+                write!(f, "unknown day annotation: {annotation}")
+            }
             ParseError::MissingTime(line) => write!(f, "missing time: {line}"),
             ParseError::MissingProject(line) => write!(f, "missing project: {line}"),
             ParseError::ErrorReading(file) => write!(f, "error reading file: {file}"),
             ParseError::InvalidPeriod(period) => write!(f, "invalid period: {period}"),
+            ParseError::InvalidConfig(reason) => write!(f, "invalid configuration: {reason}"),
+            ParseError::StrictProblems(1) => write!(f, "strict mode: 1 problem found"),
+            ParseError::StrictProblems(count) => {
+                write!(f, "strict mode: {count} problems found")
+            }
             ParseError::MultipleOutcomes(line) => write!(f, "multiple outcomes: {line}"),
-            ParseError::Located { error, location } => {
+            ParseError::InvalidWeights(line) => {
+                write!(
+                    f,
+                    "weights must be all percentages adding up to 100% or all shares: {line}"
+                )
+            }
+            ParseError::PartlyWeightedTags(line) => {
+                write!(f, "only some tags are weighted: {line}")
+            }
+            ParseError::InvalidEstimate(token) => write!(f, "invalid estimate: {token}"),
+            ParseError::MultipleTimeRanges(line) => write!(f, "multiple time ranges: {line}"),
+            ParseError::OverlappingTimeRanges(ranges) => {
+                write!(f, "overlapping time ranges: {ranges}")
+            }
+            ParseError::MultipleDurations(line) => write!(f, "multiple durations: {line}"),
+            ParseError::SubtasksExceedTime(line) => {
+                write!(f, "subtasks exceed entry time: {line}")
+            }
+            ParseError::EntryOutsideSection(line) => {
+                write!(f, "entry outside a TT section: {line}")
+            }
+            ParseError::InvalidDirective(directive) => {
+                write!(f, "invalid directive: {directive}")
+            }
+            ParseError::DayTooLong(day) => write!(f, "implausible day: {day}"),
+            ParseError::EntryTooLong(entry) => write!(f, "implausible entry: {entry}"),
+            ParseError::DuplicateEntry(original) => write!(f, "duplicate entry: {original}"),
+            ParseError::WeekendWork(day) => write!(f, "work on a weekend: {day}"),
+            ParseError::FutureDate(date) => write!(f, "date in the future: {date}"),
+            ParseError::Located {
+                error, location, ..
+            } => {
                 write!(f, "{}: line {}: {}", location.file, location.line, error)
             }
+            ParseError::InToken { error, .. } => write!(f, "{error}"),
         }
     }
 }
@@ -316,6 +972,460 @@ mod tests {
                 .expect_minutes(60 + 15 + 30);
         }
 
+        #[test]
+        fn parse_time_range() {
+            let _ = LineSpec::given_line("- #prj-alpha 09:15-10:45 design review")
+                .when_parsed()
+                .expect_valid_entry()
+                .expect_minutes(90)
+                .expect_time_range("09:15-10:45")
+                .expect_description("design review");
+        }
+
+        #[test]
+        fn parse_time_range_should_cross_midnight_when_end_before_start() {
+            let _ = LineSpec::given_line("- #ops 23:30-00:15 deploy")
+                .when_parsed()
+                .expect_valid_entry()
+                .expect_minutes(45);
+        }
+
+        #[test]
+        fn parse_time_range_should_add_to_durations() {
+            let _ = LineSpec::given_line("- #ops 9:00-10:00 15m")
+                .when_parsed()
+                .expect_valid_entry()
+                .expect_minutes(75);
+        }
+
+        #[rstest]
+        fn parse_invalid_time_range(
+            #[values("25:00-26:00", "09:60-10:00", "10:00-10:00")] range: &str,
+        ) {
+            let input = format!("- #context {range}");
+
+            LineSpec::given_line(&input)
+                .when_parsed()
+                .expect_invalid_with(&ParseError::InvalidTime(range.to_string()).in_token(11..22));
+        }
+
+        #[test]
+        fn parse_time_range_should_fail_when_multiple_ranges() {
+            let input = "- #context 09:00-10:00 11:00-12:00";
+
+            LineSpec::given_line(input)
+                .when_parsed()
+                .expect_invalid_with(&ParseError::MultipleTimeRanges(input.to_string()));
+        }
+
+        #[test]
+        fn parse_compound_duration() {
+            let _ = LineSpec::given_line("- #context 1h30m fix 1.5h flaky test")
+                .when_parsed()
+                .expect_valid_entry()
+                .expect_minutes(90 + 90)
+                .expect_description("fix flaky test");
+        }
+
+        #[test]
+        fn parse_ambiguous_duration() {
+            LineSpec::given_line("- #context 1h30 Task A")
+                .when_parsed()
+                .expect_invalid_with(&ParseError::InvalidTime("1h30".to_string()).in_token(11..15));
+        }
+
+        mod subtasks {
+            use crate::domain::config::ParseConfig;
+            use crate::domain::{ParseError, TimeEntry};
+
+            fn parent(line: &str) -> (TimeEntry, bool) {
+                let parsed = TimeEntry::parse_parent(line, &ParseConfig::default())
+                    .expect("should be an entry line")
+                    .expect("should be a valid entry");
+                (parsed.entry, parsed.has_time)
+            }
+
+            fn subtask(line: &str) -> TimeEntry {
+                TimeEntry::parse_subtask(line, &ParseConfig::default())
+                    .expect("should be a subtask line")
+                    .expect("should be a valid subtask")
+                    .entry
+            }
+
+            #[test]
+            fn entry_without_time_should_take_sum_of_subtasks() {
+                let (entry, has_time) = parent("- #dev Release");
+
+                let (entry, warning) = entry
+                    .with_subtasks(
+                        vec![subtask("- 1h write tests"), subtask("- 30m review")],
+                        has_time,
+                        "- #dev Release",
+                    )
+                    .unwrap();
+
+                assert_eq!(entry.minutes, 90);
+                assert_eq!(entry.subtasks().len(), 2);
+                assert_eq!(warning, None);
+            }
+
+            #[test]
+            fn subtask_should_inherit_tags_and_outcome() {
+                let (entry, has_time) = parent("- #dev ##release 2h Release");
+
+                let (entry, _) = entry
+                    .with_subtasks(vec![subtask("- #qa 30m review")], has_time, "")
+                    .unwrap();
+
+                let subtask = &entry.subtasks()[0];
+                let tags: Vec<String> = subtask.get_tags().iter().map(|t| t.raw_value()).collect();
+                assert_eq!(tags, vec!["dev", "qa"]);
+                assert_eq!(subtask.outcome, entry.outcome);
+            }
+
+            #[test]
+            fn entry_should_warn_when_subtasks_exceed_its_time() {
+                let line = "- #dev 1h Spike";
+                let (entry, has_time) = parent(line);
+
+                let (entry, warning) = entry
+                    .with_subtasks(vec![subtask("- 2h research")], has_time, line)
+                    .unwrap();
+
+                assert_eq!(entry.minutes, 60);
+                assert_eq!(
+                    warning,
+                    Some(ParseError::SubtasksExceedTime(line.to_string()))
+                );
+            }
+
+            #[test]
+            fn entry_should_fail_when_neither_it_nor_subtasks_have_time() {
+                let line = "- #dev Release";
+                let (entry, has_time) = parent(line);
+
+                let result = entry.with_subtasks(vec![subtask("- a note")], has_time, line);
+
+                assert_eq!(result, Err(ParseError::MissingTime(line.to_string())));
+            }
+        }
+
+        mod entry_markers {
+            use super::*;
+            use crate::domain::config::{EntryMarker, ParseConfig};
+            use crate::domain::TaskStatus;
+
+            fn config_with(entry_markers: Vec<EntryMarker>) -> ParseConfig {
+                ParseConfig {
+                    entry_markers,
+                    ..ParseConfig::default()
+                }
+            }
+
+            #[rstest]
+            fn parse_should_accept_configured_markers(
+                #[values(
+                    "- #dev 1h Task A",
+                    "* #dev 1h Task A",
+                    "+ #dev 1h Task A",
+                    "12. #dev 1h Task A",
+                    "3) #dev 1h Task A"
+                )]
+                line: &str,
+            ) {
+                let config = config_with(vec![
+                    EntryMarker::Dash,
+                    EntryMarker::Star,
+                    EntryMarker::Plus,
+                    EntryMarker::Numbered,
+                ]);
+
+                let _ = LineSpec::given_line(line)
+                    .when_parsed_with(&config)
+                    .expect_valid_entry()
+                    .expect_minutes(60)
+                    .expect_description("Task A");
+            }
+
+            #[rstest]
+            fn parse_should_ignore_markers_not_configured(
+                #[values("- #dev 1h", "+ #dev 1h", "1. #dev 1h", "*#dev 1h", ". #dev 1h")]
+                line: &str,
+            ) {
+                LineSpec::given_line(line)
+                    .when_parsed_with(&config_with(vec![EntryMarker::Star]))
+                    .expect_not_an_entry_and_not_an_error();
+            }
+
+            #[rstest]
+            #[case("- [x] #dev 1h Task A", Some(TaskStatus::Done))]
+            #[case("- [X] #dev 1h Task A", Some(TaskStatus::Done))]
+            #[case("- [ ] #dev 1h Task A", Some(TaskStatus::Open))]
+            #[case("- #dev 1h Task A", None)]
+            fn parse_should_keep_checkbox_status(
+                #[case] line: &str,
+                #[case] expected_status: Option<TaskStatus>,
+            ) {
+                let _ = LineSpec::given_line(line)
+                    .when_parsed()
+                    .expect_valid_entry()
+                    .expect_status(expected_status)
+                    .expect_context("dev")
+                    .expect_description("Task A");
+            }
+
+            #[test]
+            fn parse_should_not_treat_checkbox_without_tag_as_entry() {
+                LineSpec::given_line("- [x] buy milk")
+                    .when_parsed()
+                    .expect_not_an_entry_and_not_an_error();
+            }
+        }
+
+        mod metadata {
+            use super::*;
+
+            use crate::domain::config::ParseConfig;
+
+            fn config_with_keys() -> ParseConfig {
+                ParseConfig {
+                    metadata_keys: vec!["client".to_string(), "billable".to_string()],
+                    ..ParseConfig::default()
+                }
+            }
+
+            #[test]
+            fn parse_should_read_metadata() {
+                let _ = LineSpec::given_line("- #dev 1h client:acme billable:no Task A")
+                    .when_parsed_with(&config_with_keys())
+                    .expect_valid_entry()
+                    .expect_metadata(&[("billable", "no"), ("client", "acme")])
+                    .expect_minutes(60)
+                    .expect_description("Task A");
+            }
+
+            #[rstest]
+            fn parse_should_keep_colon_words_in_description(
+                #[values(
+                    "client:",
+                    "https://example.com",
+                    "09:00",
+                    ":acme",
+                    "TODO:fix",
+                    "re:foo"
+                )]
+                word: &str,
+            ) {
+                let _ = LineSpec::given_line(&format!("- #dev 1h {word}"))
+                    .when_parsed_with(&config_with_keys())
+                    .expect_valid_entry()
+                    .expect_metadata(&[])
+                    .expect_description(word);
+            }
+        }
+
+        mod estimates {
+            use super::*;
+
+            #[test]
+            fn parse_should_read_estimate_apart_from_time() {
+                let _ = LineSpec::given_line("- #dev 1h est:3h30m Task A")
+                    .when_parsed()
+                    .expect_valid_entry()
+                    .expect_estimate(Some(210))
+                    .expect_minutes(60)
+                    .expect_metadata(&[])
+                    .expect_description("Task A");
+            }
+
+            #[test]
+            fn parse_should_warn_and_keep_token_when_estimate_not_a_duration() {
+                let _ = LineSpec::given_line("- #dev 1h est:soon Task A")
+                    .when_parsed()
+                    .expect_warning(
+                        &ParseError::InvalidEstimate("est:soon".to_string()).in_token(10..18),
+                    )
+                    .expect_estimate(None)
+                    .expect_minutes(60)
+                    .expect_description("est:soon Task A");
+            }
+        }
+
+        mod mentions {
+            use super::*;
+
+            #[test]
+            fn parse_should_read_mentions_and_keep_them_in_description() {
+                let _ = LineSpec::given_line("- #dev 1h pairing with @alice and @bob")
+                    .when_parsed()
+                    .expect_valid_entry()
+                    .expect_people(&["alice", "bob"])
+                    .expect_description("pairing with @alice and @bob");
+            }
+
+            #[test]
+            fn parse_should_only_list_a_person_once() {
+                let _ = LineSpec::given_line("- #dev 1h @alice review @alice")
+                    .when_parsed()
+                    .expect_valid_entry()
+                    .expect_people(&["alice"]);
+            }
+
+            #[rstest]
+            fn parse_should_not_read_mentions_from(
+                #[values("@", "mail@example.com", "@2h")] word: &str,
+            ) {
+                let _ = LineSpec::given_line(&format!("- #dev 1h {word}"))
+                    .when_parsed()
+                    .expect_valid_entry()
+                    .expect_people(&[]);
+            }
+        }
+
+        mod tag_grammar {
+            use super::*;
+            use crate::domain::config::{ParseConfig, TagGrammar};
+
+            fn config_with(tags: TagGrammar) -> ParseConfig {
+                ParseConfig {
+                    tags,
+                    ..ParseConfig::default()
+                }
+            }
+
+            #[test]
+            fn parse_should_keep_numeric_tag_in_description_when_ignored() {
+                let grammar = TagGrammar {
+                    ignore_numeric: true,
+                    ..TagGrammar::default()
+                };
+
+                let _ = LineSpec::given_line("- #prj-api 1h fix #4521")
+                    .when_parsed_with(&config_with(grammar))
+                    .expect_valid_entry()
+                    .expect_tags(&["prj-api"])
+                    .expect_description("fix #4521");
+            }
+
+            #[test]
+            fn parse_should_keep_tag_after_description_in_description_when_leading_only() {
+                let grammar = TagGrammar {
+                    leading_only: true,
+                    ..TagGrammar::default()
+                };
+
+                let _ = LineSpec::given_line("- #prj-api #dev 1h fix #bug")
+                    .when_parsed_with(&config_with(grammar))
+                    .expect_valid_entry()
+                    .expect_tags(&["prj-api", "dev"])
+                    .expect_description("fix #bug");
+            }
+
+            #[rstest]
+            #[case("prj-api", true)]
+            #[case("dev_ops", false)]
+            #[case("x1", false)]
+            #[case("4521", false)]
+            fn tag_grammar_should_check_characters_and_letters(
+                #[case] tag: &str,
+                #[case] expected: bool,
+            ) {
+                let grammar = TagGrammar {
+                    allowed_chars: Some("-".to_string()),
+                    min_letters: 2,
+                    ..TagGrammar::default()
+                };
+
+                assert_eq!(grammar.accepts(tag), expected);
+            }
+        }
+
+        mod time_marker {
+            use super::*;
+            use crate::domain::config::{ParseConfig, TimeMarker};
+
+            fn config_with(time_marker: TimeMarker) -> ParseConfig {
+                ParseConfig {
+                    time_marker,
+                    ..ParseConfig::default()
+                }
+            }
+
+            #[test]
+            fn parse_should_warn_when_multiple_durations() {
+                let input = "- #context 1h fix 2h timeout bug";
+
+                let _ = LineSpec::given_line(input)
+                    .when_parsed()
+                    .expect_warning(&ParseError::MultipleDurations(input.to_string()))
+                    .expect_minutes(3 * 60);
+            }
+
+            #[test]
+            fn parse_should_only_count_first_duration_when_first_marker() {
+                let input = "- #context 1h fix 2h timeout bug";
+
+                let _ = LineSpec::given_line(input)
+                    .when_parsed_with(&config_with(TimeMarker::First))
+                    .expect_warning(&ParseError::MultipleDurations(input.to_string()))
+                    .expect_minutes(60)
+                    .expect_description("fix 2h timeout bug");
+            }
+
+            #[test]
+            fn parse_should_count_adjacent_time_tokens_as_one_duration_when_first_marker() {
+                let _ = LineSpec::given_line("- #context 1h 30m fix 2h timeout bug")
+                    .when_parsed_with(&config_with(TimeMarker::First))
+                    .expect_valid_entry()
+                    .expect_minutes(90)
+                    .expect_description("fix 2h timeout bug");
+            }
+
+            #[test]
+            fn parse_should_keep_invalid_time_after_first_duration_in_description() {
+                let _ = LineSpec::given_line("- #context 1h bump timeout to 1h30")
+                    .when_parsed_with(&config_with(TimeMarker::First))
+                    .expect_valid_entry()
+                    .expect_minutes(60)
+                    .expect_description("bump timeout to 1h30");
+            }
+
+            #[test]
+            fn parse_should_only_count_sigil_durations_when_sigil_marker() {
+                let _ = LineSpec::given_line("- #context fix 2h timeout bug @1h30m")
+                    .when_parsed_with(&config_with(TimeMarker::Sigil))
+                    .expect_valid_entry()
+                    .expect_minutes(90)
+                    .expect_description("fix 2h timeout bug");
+            }
+
+            #[test]
+            fn parse_should_read_sigil_durations_as_time_not_mentions() {
+                let _ = LineSpec::given_line("- #context review with @alice @2h")
+                    .when_parsed_with(&config_with(TimeMarker::Sigil))
+                    .expect_valid_entry()
+                    .expect_minutes(120)
+                    .expect_people(&["alice"]);
+            }
+
+            #[test]
+            fn parse_should_count_sigil_time_range_when_sigil_marker() {
+                let _ = LineSpec::given_line("- #context @09:00-10:00 standup")
+                    .when_parsed_with(&config_with(TimeMarker::Sigil))
+                    .expect_valid_entry()
+                    .expect_minutes(60);
+            }
+
+            #[test]
+            fn parse_should_fail_when_sigil_marker_and_no_sigil() {
+                let input = "- #context 2h timeout bug";
+
+                LineSpec::given_line(input)
+                    .when_parsed_with(&config_with(TimeMarker::Sigil))
+                    .expect_invalid_with(&ParseError::MissingTime(input.to_string()));
+            }
+        }
+
         #[rstest]
         fn parse_non_entries(
             #[values(
@@ -324,8 +1434,8 @@ mod tests {
                 "", // empty line
                 " ", // whitespace line
                 "some text", // text line
-                "* #not_a_tag", // alternate bullet not considered entry
-                "+ #not_a_tag", // alternate bullet not considered entry
+                "* #not_a_tag", // alternate bullet not an entry unless configured
+                "+ #not_a_tag", // alternate bullet not an entry unless configured
             )]
             line: &str,
         ) {
@@ -338,9 +1448,9 @@ mod tests {
         fn parse_invalid_time() {
             LineSpec::given_line("- #context 100000000000000000000h")
                 .when_parsed()
-                .expect_invalid_with(&ParseError::InvalidTime(
-                    "100000000000000000000h".to_string(),
-                ));
+                .expect_invalid_with(
+                    &ParseError::InvalidTime("100000000000000000000h".to_string()).in_token(11..33),
+                );
         }
 
         #[rstest]
@@ -387,7 +1497,97 @@ mod tests {
 
             LineSpec::given_line(input)
                 .when_parsed()
-                .expect_invalid_with(&ParseError::MultipleOutcomes(input.to_string()));
+                .expect_invalid_with(
+                    &ParseError::MultipleOutcomes(input.to_string()).in_token(24..41),
+                );
+        }
+
+        #[test]
+        fn parse_outcome_should_split_time_over_weighted_outcomes() {
+            let entry = LineSpec::given_line("- #project ##launch:70% ##hiring:30% 1h")
+                .when_parsed()
+                .expect_valid_entry()
+                .expect_outcome("launch");
+
+            assert_eq!(
+                entry.minutes_per_outcome(),
+                vec![("launch", 42), ("hiring", 18)]
+            );
+        }
+
+        #[test]
+        fn outcomes_as_written_should_keep_weights() {
+            let entry = LineSpec::given_line("- #project ##launch*2 ##hiring*1 1h")
+                .when_parsed()
+                .expect_valid_entry();
+
+            assert_eq!(entry.outcomes_as_written(), ["launch*2", "hiring*1"]);
+        }
+
+        #[test]
+        fn parse_should_split_time_over_weighted_tags() {
+            let entry = LineSpec::given_line("- #frontend*2 #backend*1 1h30m")
+                .when_parsed()
+                .expect_valid_entry()
+                .expect_tags(&["frontend", "backend"]);
+
+            assert_eq!(
+                entry.minutes_per_context(),
+                vec![("frontend".to_string(), 60), ("backend".to_string(), 30)]
+            );
+        }
+
+        #[rstest]
+        #[case("- #project ##launch:70% ##hiring:20% 1h", 11..23)]
+        #[case("- #project ##launch:70% ##hiring*1 1h", 11..23)]
+        #[case("- #a:50% 1h", 2..8)]
+        fn parse_should_fail_when_weights_invalid(
+            #[case] input: &str,
+            #[case] token: std::ops::Range<usize>,
+        ) {
+            LineSpec::given_line(input)
+                .when_parsed()
+                .expect_invalid_with(
+                    &ParseError::InvalidWeights(input.to_string()).in_token(token),
+                );
+        }
+
+        #[test]
+        fn parse_should_keep_colon_number_in_tag() {
+            let entry = LineSpec::given_line("- #sprint:12 1h")
+                .when_parsed()
+                .expect_valid_entry()
+                .expect_tags(&["sprint:12"]);
+
+            assert_eq!(
+                entry.minutes_per_context(),
+                vec![("sprint:12".to_string(), 60)]
+            );
+        }
+
+        #[test]
+        fn parse_should_warn_when_only_some_tags_weighted() {
+            let input = "- #dev #a*1 #b*1 1h";
+
+            let entry = LineSpec::given_line(input)
+                .when_parsed()
+                .expect_warning(&ParseError::PartlyWeightedTags(input.to_string()));
+
+            assert_eq!(
+                entry.minutes_per_context(),
+                vec![("a".to_string(), 30), ("b".to_string(), 30)]
+            );
+        }
+
+        #[test]
+        fn parse_outcome_should_fail_when_not_all_outcomes_weighted() {
+            let input = "- #project ##launch:70% ##hiring 1h";
+
+            LineSpec::given_line(input)
+                .when_parsed()
+                .expect_invalid_with(
+                    &ParseError::MultipleOutcomes(input.to_string()).in_token(24..32),
+                );
         }
 
         #[test]
@@ -439,7 +1639,8 @@ mod tests {
         }
 
         mod spec {
-            use crate::domain::{EntryLineParseResult, Outcome, ParseError, TimeEntry};
+            use crate::domain::config::ParseConfig;
+            use crate::domain::{EntryLineParseResult, Outcome, ParseError, TaskStatus, TimeEntry};
 
             pub struct LineSpec {
                 line: String,
@@ -456,6 +1657,11 @@ mod tests {
                     let outcome = TimeEntry::parse(&self.line);
                     LineParsingResult { outcome }
                 }
+
+                pub fn when_parsed_with(self, config: &ParseConfig) -> LineParsingResult {
+                    let outcome = TimeEntry::parse_with_config(&self.line, config);
+                    LineParsingResult { outcome }
+                }
             }
 
             pub struct LineParsingResult {
@@ -471,7 +1677,30 @@ mod tests {
                 #[must_use]
                 pub fn expect_valid_entry(self) -> TimeEntry {
                     match self.outcome {
-                        EntryLineParseResult::Entry(entry) => entry,
+                        EntryLineParseResult::Entry { entry, .. } => *entry,
+                        EntryLineParseResult::NotAnEntry => {
+                            panic!("Expected time entry but line was not an entry")
+                        }
+                        EntryLineParseResult::Malformed(err) => {
+                            panic!("Expected time entry but was error: {err:?}")
+                        }
+                    }
+                }
+
+                /// Asserts that this line parsing result is a valid entry with the expected warning.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the parsing result is not an entry or lacks the warning.
+                pub fn expect_warning(self, expected_warning: &ParseError) -> TimeEntry {
+                    match self.outcome {
+                        EntryLineParseResult::Entry { entry, warnings } => {
+                            assert!(
+                                warnings.contains(expected_warning),
+                                "Expected warning {expected_warning:?} but got {warnings:?}"
+                            );
+                            *entry
+                        }
                         EntryLineParseResult::NotAnEntry => {
                             panic!("Expected time entry but line was not an entry")
                         }
@@ -489,7 +1718,7 @@ mod tests {
                 pub fn expect_not_an_entry_and_not_an_error(self) {
                     match self.outcome {
                         EntryLineParseResult::NotAnEntry => {}
-                        EntryLineParseResult::Entry(_) => {
+                        EntryLineParseResult::Entry { .. } => {
                             panic!("Expected no entry but found entry")
                         }
                         EntryLineParseResult::Malformed(err) => {
@@ -508,7 +1737,7 @@ mod tests {
                         EntryLineParseResult::Malformed(error) => {
                             assert_eq!(error, *expected_error);
                         }
-                        EntryLineParseResult::Entry(_) => {
+                        EntryLineParseResult::Entry { .. } => {
                             panic!("Expected error but was valid entry")
                         }
                         EntryLineParseResult::NotAnEntry => {
@@ -541,6 +1770,22 @@ mod tests {
                     self
                 }
 
+                /// Asserts that this time entry has exactly the expected tags, in order.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the tags don't match the expected values.
+                #[must_use]
+                pub fn expect_tags(self, expected_tags: &[&str]) -> TimeEntry {
+                    let tags: Vec<String> = self
+                        .tags
+                        .iter()
+                        .map(crate::domain::tags::Tag::raw_value)
+                        .collect();
+                    assert_eq!(tags, expected_tags);
+                    self
+                }
+
                 /// Asserts that this time entry has the expected description.
                 ///
                 /// # Panics
@@ -577,6 +1822,69 @@ mod tests {
                     self
                 }
 
+                /// Asserts that this time entry was logged with the expected clock range.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the entry has no clock range or a different one.
+                #[must_use]
+                pub fn expect_time_range(self, expected_range: &str) -> TimeEntry {
+                    assert_eq!(
+                        self.time_range.map(|range| range.to_string()),
+                        Some(expected_range.to_string())
+                    );
+                    self
+                }
+
+                /// Asserts that this time entry has exactly the expected metadata.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the metadata doesn't match the expected key/value pairs.
+                #[must_use]
+                pub fn expect_metadata(self, expected_metadata: &[(&str, &str)]) -> TimeEntry {
+                    let metadata: Vec<(&str, &str)> = self
+                        .metadata
+                        .iter()
+                        .map(|(key, value)| (key.as_str(), value.as_str()))
+                        .collect();
+                    assert_eq!(metadata, expected_metadata);
+                    self
+                }
+
+                /// Asserts that this time entry has the expected estimate.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the estimate doesn't match the expected minutes.
+                #[must_use]
+                pub fn expect_estimate(self, expected_estimate: Option<u32>) -> TimeEntry {
+                    assert_eq!(self.estimate, expected_estimate);
+                    self
+                }
+
+                /// Asserts that this time entry mentions exactly the expected people.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the people don't match the expected names.
+                #[must_use]
+                pub fn expect_people(self, expected_people: &[&str]) -> TimeEntry {
+                    assert_eq!(self.people, expected_people);
+                    self
+                }
+
+                /// Asserts that this time entry has the expected checkbox status.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the status doesn't match the expected value.
+                #[must_use]
+                pub fn expect_status(self, expected_status: Option<TaskStatus>) -> TimeEntry {
+                    assert_eq!(self.status, expected_status);
+                    self
+                }
+
                 /// Asserts that this time entry has no outcome.
                 ///
                 /// # Panics
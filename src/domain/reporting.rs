@@ -2,8 +2,10 @@ use std::collections::HashMap;
 use std::hash::Hash;
 
 use super::dates::{EndDate, StartDate};
-use super::tags::Tag;
-use super::{ParseError, PeriodRequested, TimeEntry};
+use super::days::{DayRecord, Leave, WorkingDays};
+use super::tags::{self, Tag};
+use super::tickets::TicketUrl;
+use super::{ParseError, PeriodRequested, TaskStatus, TimeEntry};
 use chrono::NaiveDate;
 use chrono::{Datelike, IsoWeek};
 use itertools::Itertools;
@@ -21,6 +23,8 @@ pub struct TrackedTime {
     pub period: TrackingPeriod,
     pub total_minutes: u32,
     pub entries_by_date: std::collections::HashMap<NaiveDate, Vec<TimeEntry>>,
+    /// Annotations of the TT headers, e.g. `[holiday]`, for days that have any.
+    pub day_records: HashMap<NaiveDate, DayRecord>,
 }
 
 impl TrackedTime {
@@ -32,6 +36,7 @@ impl TrackedTime {
             period: TrackingPeriod::new(start, end, days),
             total_minutes,
             entries_by_date: std::collections::HashMap::new(),
+            day_records: HashMap::new(),
         }
     }
 
@@ -49,9 +54,50 @@ impl TrackedTime {
             period: TrackingPeriod::new(start, end, days),
             total_minutes,
             entries_by_date,
+            day_records: HashMap::new(),
         }
     }
 
+    /// Adds the annotated days, counting their leave in the period's working days.
+    #[must_use]
+    pub fn with_day_records(mut self, day_records: HashMap<NaiveDate, DayRecord>) -> Self {
+        self.period.working_days = WorkingDays::count(self.entries_by_date.keys(), &day_records);
+        self.day_records = day_records;
+        self
+    }
+
+    /// The tracked time without the entries on holidays and sick days, or `None` when no entries
+    /// are left. Half days are kept.
+    #[must_use]
+    pub fn excluding_leave(&self) -> Option<TrackedTime> {
+        let is_leave = |date: &NaiveDate| {
+            self.day_records
+                .get(date)
+                .and_then(|record| record.leave)
+                .is_some_and(Leave::is_full_day)
+        };
+        let entries_by_date: HashMap<NaiveDate, Vec<TimeEntry>> = self
+            .entries_by_date
+            .iter()
+            .filter(|(date, _)| !is_leave(date))
+            .map(|(date, entries)| (*date, entries.clone()))
+            .collect();
+        let start = entries_by_date.keys().min().map(|&date| StartDate(date))?;
+        let end = entries_by_date.keys().max().map(|&date| EndDate(date))?;
+        let days = u32::try_from(entries_by_date.len()).unwrap_or(u32::MAX);
+        let entries = entries_by_date.values().flatten().cloned().collect();
+        let day_records = self
+            .day_records
+            .iter()
+            .filter(|(date, _)| !is_leave(date))
+            .map(|(date, record)| (*date, record.clone()))
+            .collect();
+        Some(
+            TrackedTime::with_entries_by_date(entries, entries_by_date, start, end, days)
+                .with_day_records(day_records),
+        )
+    }
+
     #[must_use]
     pub fn tasks_tracked_for(&self, tags: &[Tag]) -> DetailReport {
         let mut per_tag_summaries = Vec::new();
@@ -64,46 +110,218 @@ impl TrackedTime {
         DetailReport::new(per_tag_summaries, self.period, self.total_minutes)
     }
 
-    fn summarize_tasks_for_context(&self, tag: &Tag) -> TaskSummariesForContext {
-        let total_times_for_tasks = self.calculate_totals_for_tasks(tag);
+    /// Details per group, e.g. one section per `client:` value or per person.
+    #[must_use]
+    pub fn tasks_tracked_by(&self, group_by: &GroupBy) -> DetailReport {
+        let summaries = group_by
+            .labels_of_all(&self.entries)
+            .into_iter()
+            .map(|label| {
+                let entries = self
+                    .entries
+                    .iter()
+                    .filter(|entry| group_by.labels(entry).contains(&label))
+                    .collect();
+                summarize_tasks(Tag::Context(label), entries)
+            })
+            .collect();
+
+        DetailReport::new(summaries, self.period, self.total_minutes)
+    }
+
+    /// Tasks with an `est:` estimate per main context, next to the time tracked on them.
+    #[must_use]
+    pub fn estimates_tracked(&self) -> EstimateReport {
+        let summaries = self
+            .entries
+            .iter()
+            .map(TimeEntry::main_context)
+            .unique()
+            .sorted()
+            .filter_map(|context| {
+                let entries: Vec<&TimeEntry> = self
+                    .entries
+                    .iter()
+                    .filter(|entry| entry.main_context() == context)
+                    .collect();
+                let tasks = estimate_tasks(&entries);
+                (!tasks.is_empty())
+                    .then(|| TaskEstimatesForContext::new(Tag::from_raw(&context), tasks))
+            })
+            .collect();
+
+        EstimateReport::new(summaries, self.period)
+    }
 
-        let total_time_for_tag: u32 = total_times_for_tasks.values().sum();
+    /// Entries in the order they were written: by date, then by file and line.
+    #[must_use]
+    pub fn entries_in_order(&self) -> Vec<&TimeEntry> {
+        self.entries
+            .iter()
+            .sorted_by_key(|entry| {
+                let source = entry
+                    .source
+                    .as_ref()
+                    .map(|source| (source.file.as_str(), source.line));
+                (entry.date, source)
+            })
+            .collect()
+    }
 
-        let task_summaries = total_times_for_tasks
+    /// Entries with the tag or a tag below it, in chronological order.
+    #[must_use]
+    pub fn entries_tagged(&self, tag: &Tag) -> Vec<&TimeEntry> {
+        self.entries_in_order()
             .into_iter()
-            .map(|(desc, minutes)| TaskSummary::new(desc, minutes, total_time_for_tag));
-        let sorted_summaries = TrackedTime::sort_by_time(task_summaries).collect::<Vec<_>>();
+            .filter(|entry| entry.tags.iter().any(|entry_tag| entry_tag.is_within(tag)))
+            .collect()
+    }
 
-        TaskSummariesForContext::new(tag.clone(), sorted_summaries)
+    /// Every entry with the day and place it was written, in chronological order.
+    #[must_use]
+    pub fn log(&self) -> LogReport {
+        let entries = self.entries_in_order().into_iter().cloned().collect();
+        LogReport::new(entries, self.period, self.total_minutes)
     }
 
-    fn calculate_totals_for_tasks(&self, tag: &Tag) -> HashMap<String, u32> {
-        sum_time_by_key(
-            self.entries.iter().filter(|entry| entry.tags.contains(tag)),
-            |entry| {
-                Some(
-                    entry
-                        .description
-                        .clone()
-                        .unwrap_or_else(|| "<no description>".to_string()),
-                )
-            },
-        )
+    /// Tasks of the entries with the tag, counting the tag's part of weighted entries.
+    fn summarize_tasks_for_context(&self, tag: &Tag) -> TaskSummariesForContext {
+        let entries_for_tag: Vec<TimeEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.tags.iter().any(|entry_tag| entry_tag.is_within(tag)))
+            .map(|entry| entry.counted_for(tag))
+            .collect();
+        summarize_tasks(tag.clone(), entries_for_tag.iter().collect())
     }
+}
 
-    fn sort_by_time(
-        map: std::iter::Map<
-            std::collections::hash_map::IntoIter<String, u32>,
-            impl FnMut((String, u32)) -> TaskSummary,
-        >,
-    ) -> std::vec::IntoIter<TaskSummary> {
-        map.sorted_by(|a, b| b.minutes.cmp(&a.minutes))
+/// Task totals of the given entries, each task followed by its subtasks.
+fn summarize_tasks(context: Tag, entries: Vec<&TimeEntry>) -> TaskSummariesForContext {
+    let total_minutes: u32 = entries.iter().map(|entry| entry.minutes).sum();
+
+    let tasks = summarize_by_description(entries.iter().copied(), total_minutes);
+    let task_summaries = tasks
+        .into_iter()
+        .flat_map(|task| {
+            let subtasks = entries
+                .iter()
+                .filter(|entry| describe(entry) == task.description)
+                .flat_map(|entry| entry.subtasks());
+            let subtask_summaries = summarize_by_description(subtasks, total_minutes)
+                .into_iter()
+                .map(|subtask| subtask.at_level(1));
+            std::iter::once(task).chain(subtask_summaries)
+        })
+        .collect();
+
+    TaskSummariesForContext::new(context, task_summaries)
+}
+
+/// Grouping of time other than by project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupBy {
+    /// Per value of a metadata key, e.g. `client`; entries without the key show as `<no client>`.
+    Metadata(String),
+    /// Per person mentioned with `@name`, ignoring case; an entry counts fully for each of its
+    /// people and entries without people are left out.
+    People,
+    /// Per ticket key in the description, e.g. `PROJ-1234`, linked with the URL template if any;
+    /// an entry counts fully for each of its tickets and entries without one show as `<no ticket>`.
+    Tickets(Option<TicketUrl>),
+}
+
+impl GroupBy {
+    /// The groups an entry's time counts for.
+    fn labels(&self, entry: &TimeEntry) -> Vec<String> {
+        match self {
+            GroupBy::Metadata(key) => vec![entry
+                .metadata
+                .get(key)
+                .cloned()
+                .unwrap_or_else(|| format!("<no {key}>"))],
+            GroupBy::People => entry
+                .people
+                .iter()
+                .map(|name| format!("@{}", name.to_lowercase()))
+                .collect(),
+            GroupBy::Tickets(_) if entry.tickets.is_empty() => vec!["<no ticket>".to_string()],
+            GroupBy::Tickets(_) => entry.tickets.clone(),
+        }
+    }
+
+    /// The link of a group, for tickets with a URL template.
+    fn link(&self, label: &str) -> Option<String> {
+        match self {
+            GroupBy::Tickets(Some(url)) if label != "<no ticket>" => Some(url.link(label)),
+            _ => None,
+        }
+    }
+
+    /// Distinct groups of the entries, in alphabetical order.
+    fn labels_of_all(&self, entries: &[TimeEntry]) -> Vec<String> {
+        entries
+            .iter()
+            .flat_map(|entry| self.labels(entry))
+            .unique()
+            .sorted()
+            .collect()
     }
 }
 
+/// Estimated tasks of the given entries, largest actual time first.
+///
+/// A task's estimate is the largest one given on its entries, so an estimate repeated
+/// on every day the task is worked on counts once.
+fn estimate_tasks(entries: &[&TimeEntry]) -> Vec<TaskEstimate> {
+    entries
+        .iter()
+        .filter(|entry| entry.estimate.is_some())
+        .map(|entry| describe(entry))
+        .unique()
+        .map(|description| {
+            let task_entries = entries
+                .iter()
+                .filter(|entry| describe(entry) == description);
+            let estimate_minutes = task_entries
+                .clone()
+                .filter_map(|entry| entry.estimate)
+                .max()
+                .unwrap_or_default();
+            let actual_minutes = task_entries.map(|entry| entry.minutes).sum();
+            TaskEstimate::new(description, estimate_minutes, actual_minutes)
+        })
+        .sorted_by(|a, b| {
+            b.actual_minutes
+                .cmp(&a.actual_minutes)
+                .then(a.description.cmp(&b.description))
+        })
+        .collect()
+}
+
+/// Sums time per task description, largest first.
+fn summarize_by_description<'a>(
+    entries: impl Iterator<Item = &'a TimeEntry>,
+    total_minutes: u32,
+) -> Vec<TaskSummary> {
+    sum_time_by_key(entries, |entry| Some(describe(entry)))
+        .into_iter()
+        .map(|(description, minutes)| TaskSummary::new(description, minutes, total_minutes))
+        .sorted_by(|a, b| b.minutes.cmp(&a.minutes))
+        .collect()
+}
+
+fn describe(entry: &TimeEntry) -> String {
+    entry
+        .description
+        .clone()
+        .unwrap_or_else(|| "<no description>".to_string())
+}
+
 pub struct OverviewReport {
     entries_total_time: Vec<TimeTotal>,
     outcomes_total_time: Vec<TimeTotal>,
+    status_total_time: Vec<TimeTotal>,
     period: TrackingPeriod,
     period_requested: Option<PeriodRequested>,
     total_minutes: u32,
@@ -118,16 +336,47 @@ impl OverviewReport {
     ) -> Self {
         let entries_summed = sum_time_entries(time_report, limit);
         let outcomes_summed = sum_outcomes(time_report, limit);
+        let statuses_summed = sum_statuses(time_report);
 
         OverviewReport {
             entries_total_time: entries_summed,
             outcomes_total_time: outcomes_summed,
+            status_total_time: statuses_summed,
             period: time_report.period,
             period_requested: period_requested.cloned(),
             total_minutes: time_report.total_minutes,
         }
     }
 
+    /// Overview with project totals rolled up along their tag paths,
+    /// e.g. `client` > `acme` > `meeting`, down to the given depth.
+    ///
+    /// The limit applies to the top-level totals, each kept with its subtree.
+    #[must_use]
+    pub fn overview_at_depth(
+        time_report: &TrackedTime,
+        limit: Option<&OutputLimit>,
+        period_requested: Option<&PeriodRequested>,
+        depth: usize,
+    ) -> Self {
+        let mut report = Self::overview(time_report, None, period_requested);
+        report.entries_total_time = sum_time_entries_as_tree(time_report, limit, depth);
+        report
+    }
+
+    /// Overview with totals per group, e.g. per `client` value or per person, instead of per project.
+    #[must_use]
+    pub fn overview_grouped_by(
+        time_report: &TrackedTime,
+        limit: Option<&OutputLimit>,
+        period_requested: Option<&PeriodRequested>,
+        group_by: &GroupBy,
+    ) -> Self {
+        let mut report = Self::overview(time_report, limit, period_requested);
+        report.entries_total_time = sum_time_by_group(time_report, limit, group_by);
+        report
+    }
+
     #[must_use]
     pub fn entries_time_totals(&self) -> &Vec<TimeTotal> {
         &self.entries_total_time
@@ -138,6 +387,12 @@ impl OverviewReport {
         &self.outcomes_total_time
     }
 
+    /// Time of task checkbox entries split into done and open, empty when there are none.
+    #[must_use]
+    pub fn status_time_totals(&self) -> &Vec<TimeTotal> {
+        &self.status_total_time
+    }
+
     #[must_use]
     pub fn period(&self) -> &TrackingPeriod {
         &self.period
@@ -156,7 +411,35 @@ impl OverviewReport {
 
 fn sum_time_entries(time_report: &TrackedTime, limit: Option<&OutputLimit>) -> Vec<TimeTotal> {
     let summed_entries = sum_entries(&time_report.entries);
+    sorted_time_totals(summed_entries, time_report, limit)
+}
 
+fn sum_time_by_group(
+    time_report: &TrackedTime,
+    limit: Option<&OutputLimit>,
+    group_by: &GroupBy,
+) -> Vec<TimeTotal> {
+    let mut summed_groups: HashMap<String, u32> = HashMap::new();
+    for entry in &time_report.entries {
+        for label in group_by.labels(entry) {
+            *summed_groups.entry(label).or_default() += entry.minutes;
+        }
+    }
+    sorted_time_totals(summed_groups.into_iter().collect(), time_report, limit)
+        .into_iter()
+        .map(|total| {
+            let link = group_by.link(&total.description);
+            total.with_link(link)
+        })
+        .collect()
+}
+
+/// Totals largest first, cut off at the limit.
+fn sorted_time_totals(
+    summed_entries: Vec<(String, u32)>,
+    time_report: &TrackedTime,
+    limit: Option<&OutputLimit>,
+) -> Vec<TimeTotal> {
     let summed_entries_sorted = summed_entries
         .into_iter()
         .map(|(project, minutes)| TimeTotal::new(project, minutes, time_report.total_minutes))
@@ -175,9 +458,97 @@ fn sum_time_entries(time_report: &TrackedTime, limit: Option<&OutputLimit>) -> V
     }
 }
 
+fn sum_time_entries_as_tree(
+    time_report: &TrackedTime,
+    limit: Option<&OutputLimit>,
+    depth: usize,
+) -> Vec<TimeTotal> {
+    let nodes = roll_up_tag_paths(
+        time_report
+            .entries
+            .iter()
+            .flat_map(TimeEntry::minutes_per_context),
+        depth,
+    );
+    let to_total = |node: &TagTreeNode| {
+        TimeTotal::new(
+            tags::path_leaf(&node.path).to_string(),
+            node.minutes,
+            time_report.total_minutes,
+        )
+        .at_level(node.level)
+    };
+
+    let top_level: Vec<TimeTotal> = nodes
+        .iter()
+        .filter(|node| node.level == 0)
+        .map(to_total)
+        .collect();
+    let kept_top_level = match limit {
+        Some(OutputLimit::CumulativePercentageThreshold(threshold)) => {
+            let total_minutes = f64::from(time_report.total_minutes);
+            limit_number_of_entries(total_minutes, top_level.into_iter(), *threshold)
+        }
+        None => top_level,
+    };
+
+    nodes
+        .iter()
+        .filter(|node| {
+            let root = tags::path_prefixes(&node.path, 1)[0];
+            kept_top_level.iter().any(|total| total.description == root)
+        })
+        .map(to_total)
+        .collect()
+}
+
+/// Total time of a tag path and all paths below it.
+struct TagTreeNode {
+    path: String,
+    level: usize,
+    minutes: u32,
+}
+
+/// Sums time per tag path prefix, down to the given depth, and lists the
+/// resulting tree in display order: every node followed by its children, largest first.
+fn roll_up_tag_paths(paths: impl Iterator<Item = (String, u32)>, depth: usize) -> Vec<TagTreeNode> {
+    let mut summed: HashMap<String, u32> = HashMap::new();
+    for (path, minutes) in paths {
+        for prefix in tags::path_prefixes(&path, depth) {
+            *summed.entry(prefix.to_string()).or_default() += minutes;
+        }
+    }
+
+    let mut nodes = Vec::new();
+    push_tag_tree_nodes(&summed, None, 0, &mut nodes);
+    nodes
+}
+
+fn push_tag_tree_nodes(
+    summed: &HashMap<String, u32>,
+    parent: Option<&str>,
+    level: usize,
+    nodes: &mut Vec<TagTreeNode>,
+) {
+    let children = summed
+        .iter()
+        .filter(|(path, _)| tags::parent_path(path) == parent)
+        .sorted_by(|(path_a, minutes_a), (path_b, minutes_b)| {
+            minutes_b.cmp(minutes_a).then(path_a.cmp(path_b))
+        });
+    for (path, minutes) in children {
+        nodes.push(TagTreeNode {
+            path: path.clone(),
+            level,
+            minutes: *minutes,
+        });
+        push_tag_tree_nodes(summed, Some(path), level + 1, nodes);
+    }
+}
+
 fn limit_number_of_entries(
     total_minutes: f64,
-    totals: std::vec::IntoIter<TimeTotal>,
+    totals: impl Iterator<Item = TimeTotal>,
     cumulative_percentage_threshold: f64,
 ) -> Vec<TimeTotal> {
     let mut result = Vec::new();
@@ -212,19 +583,22 @@ where
     aggregated
 }
 
+/// Sums time per main context, with the time of weighted tags split between them.
 fn sum_entries(entries: &[TimeEntry]) -> Vec<(String, u32)> {
-    sum_time_by_key(entries.iter(), |entry| Some(entry.main_context().clone()))
-        .into_iter()
-        .collect()
+    let mut summed: HashMap<String, u32> = HashMap::new();
+    for (context, minutes) in entries.iter().flat_map(TimeEntry::minutes_per_context) {
+        *summed.entry(context).or_default() += minutes;
+    }
+    summed.into_iter().collect()
 }
 
 fn sum_outcomes(time_report: &TrackedTime, limit: Option<&OutputLimit>) -> Vec<TimeTotal> {
-    let summed_outcomes = sum_time_by_key(time_report.entries.iter(), |entry| {
-        entry
-            .outcome
-            .as_ref()
-            .map(|outcome| outcome.description().to_string())
-    });
+    let mut summed_outcomes: HashMap<String, u32> = HashMap::new();
+    for entry in &time_report.entries {
+        for (outcome, minutes) in entry.minutes_per_outcome() {
+            *summed_outcomes.entry(outcome.to_string()).or_default() += minutes;
+        }
+    }
 
     let summed_outcomes_sorted = summed_outcomes
         .into_iter()
@@ -244,11 +618,32 @@ fn sum_outcomes(time_report: &TrackedTime, limit: Option<&OutputLimit>) -> Vec<T
     }
 }
 
+fn sum_statuses(time_report: &TrackedTime) -> Vec<TimeTotal> {
+    let summed_statuses = sum_time_by_key(time_report.entries.iter(), |entry| entry.status);
+
+    [TaskStatus::Done, TaskStatus::Open]
+        .into_iter()
+        .filter_map(|status| {
+            summed_statuses.get(&status).map(|minutes| {
+                TimeTotal::new(
+                    status.description().to_string(),
+                    *minutes,
+                    time_report.total_minutes,
+                )
+            })
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct TimeTotal {
     pub(crate) description: String,
     pub(crate) minutes: u32,
     pub(crate) percentage: u32,
+    /// Nesting in a tag tree, 0 for top-level totals.
+    pub(crate) level: usize,
+    /// Where the total's subject lives, e.g. the page of a ticket.
+    pub(crate) link: Option<String>,
 }
 
 impl TimeTotal {
@@ -258,8 +653,22 @@ impl TimeTotal {
             description,
             minutes,
             percentage: calculate_percentage(minutes, total_minutes),
+            level: 0,
+            link: None,
         }
     }
+
+    #[must_use]
+    pub fn at_level(mut self, level: usize) -> Self {
+        self.level = level;
+        self
+    }
+
+    #[must_use]
+    pub fn with_link(mut self, link: Option<String>) -> Self {
+        self.link = link;
+        self
+    }
 }
 
 pub struct DetailReport {
@@ -319,9 +728,14 @@ impl TaskSummariesForContext {
         &self.entries
     }
 
+    /// Total of the top-level tasks; subtask time is part of their parent's.
     #[must_use]
     pub fn total_minutes(&self) -> u32 {
-        self.entries.iter().map(|entry| entry.minutes).sum()
+        self.entries
+            .iter()
+            .filter(|entry| entry.level == 0)
+            .map(|entry| entry.minutes)
+            .sum()
     }
 }
 
@@ -330,6 +744,8 @@ pub struct TaskSummary {
     pub(crate) description: String,
     pub(crate) minutes: u32,
     pub(crate) percentage_of_total: u32,
+    /// Nesting in a tag tree, 0 for top-level summaries.
+    pub(crate) level: usize,
 }
 
 impl TaskSummary {
@@ -339,8 +755,129 @@ impl TaskSummary {
             description,
             minutes,
             percentage_of_total: calculate_percentage(minutes, total_minutes),
+            level: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn at_level(mut self, level: usize) -> Self {
+        self.level = level;
+        self
+    }
+}
+
+pub struct LogReport {
+    entries: Vec<TimeEntry>,
+    period: TrackingPeriod,
+    total_minutes: u32,
+}
+
+impl LogReport {
+    #[must_use]
+    pub fn new(entries: Vec<TimeEntry>, period: TrackingPeriod, total_minutes: u32) -> Self {
+        Self {
+            entries,
+            period,
+            total_minutes,
         }
     }
+
+    /// The entries in chronological order.
+    #[must_use]
+    pub fn entries(&self) -> &Vec<TimeEntry> {
+        &self.entries
+    }
+
+    #[must_use]
+    pub fn period(&self) -> &TrackingPeriod {
+        &self.period
+    }
+
+    #[must_use]
+    pub fn total_minutes(&self) -> u32 {
+        self.total_minutes
+    }
+}
+
+pub struct EstimateReport {
+    summaries: Vec<TaskEstimatesForContext>,
+    period: TrackingPeriod,
+}
+
+impl EstimateReport {
+    #[must_use]
+    pub fn new(summaries: Vec<TaskEstimatesForContext>, period: TrackingPeriod) -> Self {
+        Self { summaries, period }
+    }
+
+    #[must_use]
+    pub fn summaries(&self) -> &Vec<TaskEstimatesForContext> {
+        &self.summaries
+    }
+
+    #[must_use]
+    pub fn period(&self) -> &TrackingPeriod {
+        &self.period
+    }
+}
+
+#[derive(Debug)]
+pub struct TaskEstimatesForContext {
+    context: Tag,
+    tasks: Vec<TaskEstimate>,
+}
+
+impl TaskEstimatesForContext {
+    fn new(context: Tag, tasks: Vec<TaskEstimate>) -> Self {
+        Self { context, tasks }
+    }
+
+    #[must_use]
+    pub fn context(&self) -> &Tag {
+        &self.context
+    }
+
+    #[must_use]
+    pub fn tasks(&self) -> &Vec<TaskEstimate> {
+        &self.tasks
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskEstimate {
+    pub(crate) description: String,
+    pub(crate) estimate_minutes: u32,
+    pub(crate) actual_minutes: u32,
+}
+
+impl TaskEstimate {
+    #[must_use]
+    pub fn new(description: String, estimate_minutes: u32, actual_minutes: u32) -> Self {
+        Self {
+            description,
+            estimate_minutes,
+            actual_minutes,
+        }
+    }
+
+    /// Minutes spent over the estimate, negative when under it.
+    #[must_use]
+    pub fn variance_minutes(&self) -> i64 {
+        i64::from(self.actual_minutes) - i64::from(self.estimate_minutes)
+    }
+
+    /// The variance as a rounded percentage of the estimate, e.g. `17` for 3h 30m against 3h;
+    /// `None` for an estimate of zero.
+    #[must_use]
+    pub fn variance_percentage(&self) -> Option<i64> {
+        (self.estimate_minutes > 0).then(|| {
+            let percentage =
+                (self.variance_minutes() as f64 / f64::from(self.estimate_minutes)) * 100.0;
+            #[allow(clippy::cast_possible_truncation)]
+            let result = percentage.round() as i64;
+            result
+        })
+    }
 }
 
 fn calculate_percentage(minutes: u32, total_minutes: u32) -> u32 {
@@ -428,12 +965,22 @@ pub struct TrackingPeriod {
     pub(crate) start: StartDate,
     pub(crate) end: EndDate,
     pub(crate) days: u32,
+    /// Days including leave without entries, and the leave taken on them.
+    pub(crate) working_days: WorkingDays,
 }
 
 impl TrackingPeriod {
     #[must_use]
     pub fn new(start: StartDate, end: EndDate, days: u32) -> Self {
-        Self { start, end, days }
+        Self {
+            start,
+            end,
+            days,
+            working_days: WorkingDays {
+                days,
+                ..WorkingDays::default()
+            },
+        }
     }
 }
 
@@ -488,6 +1035,25 @@ impl BreakdownReport {
 
     #[must_use]
     pub fn from_tracked_time(time_report: &TrackedTime, unit: BreakdownUnit, include_details: bool) -> Self {
+        let details = include_details.then_some(TaskDetails::Flat);
+        Self::with_task_details(time_report, unit, details)
+    }
+
+    /// Breakdown with the tasks of each period rolled up along their tag paths, down to the given depth.
+    #[must_use]
+    pub fn from_tracked_time_at_depth(
+        time_report: &TrackedTime,
+        unit: BreakdownUnit,
+        depth: usize,
+    ) -> Self {
+        Self::with_task_details(time_report, unit, Some(TaskDetails::Tree(depth)))
+    }
+
+    fn with_task_details(
+        time_report: &TrackedTime,
+        unit: BreakdownUnit,
+        include_details: Option<TaskDetails>,
+    ) -> Self {
         let groups = match unit {
             BreakdownUnit::Day => break_down_by_day_with_dates(&time_report.entries_by_date, include_details),
             BreakdownUnit::Week => break_down_by_week_with_entries(&time_report.entries_by_date, include_details),
@@ -503,20 +1069,39 @@ impl BreakdownReport {
     }
 }
 
-fn aggregate_tasks_from_entries(entries: &[TimeEntry]) -> Vec<TaskSummary> {
+/// How the tasks of a breakdown period are listed.
+#[derive(Debug, Clone, Copy)]
+enum TaskDetails {
+    /// One line per first tag.
+    Flat,
+    /// Tag paths rolled up into a tree down to the given depth.
+    Tree(usize),
+}
+
+fn aggregate_tasks_from_entries(entries: &[TimeEntry], details: TaskDetails) -> Vec<TaskSummary> {
     let total_minutes: u32 = entries.iter().map(|e| e.minutes).sum();
     if total_minutes == 0 {
         return vec![];
     }
 
+    if let TaskDetails::Tree(depth) = details {
+        let paths = entries.iter().flat_map(TimeEntry::minutes_per_context);
+        return roll_up_tag_paths(paths, depth)
+            .into_iter()
+            .map(|node| {
+                TaskSummary::new(
+                    tags::path_leaf(&node.path).to_string(),
+                    node.minutes,
+                    total_minutes,
+                )
+                .at_level(node.level)
+            })
+            .collect();
+    }
+
     let mut task_totals: HashMap<String, u32> = HashMap::new();
-    for entry in entries {
-        let tag_label = entry
-            .tags
-            .first()
-            .map(|tag| tag.raw_value().clone())
-            .unwrap_or_else(|| "<no tag>".to_string());
-        *task_totals.entry(tag_label).or_insert(0) += entry.minutes;
+    for (tag_label, minutes) in entries.iter().flat_map(TimeEntry::minutes_per_context) {
+        *task_totals.entry(tag_label).or_insert(0) += minutes;
     }
 
     let mut tasks: Vec<TaskSummary> = task_totals
@@ -524,21 +1109,19 @@ fn aggregate_tasks_from_entries(entries: &[TimeEntry]) -> Vec<TaskSummary> {
         .map(|(tag, minutes)| TaskSummary::new(tag, minutes, total_minutes))
         .collect();
 
-    tasks.sort_by(|a, b| b.minutes.cmp(&a.minutes));
+    tasks.sort_by_key(|task| std::cmp::Reverse(task.minutes));
     tasks
 }
 
 fn build_breakdown_group(
     label: String,
     entries: &[TimeEntry],
-    include_details: bool,
+    include_details: Option<TaskDetails>,
 ) -> BreakdownGroup {
     let total_minutes: u32 = entries.iter().map(|e| e.minutes).sum();
-    let tasks = if include_details {
-        aggregate_tasks_from_entries(entries)
-    } else {
-        vec![]
-    };
+    let tasks = include_details
+        .map(|details| aggregate_tasks_from_entries(entries, details))
+        .unwrap_or_default();
     BreakdownGroup {
         label,
         minutes: total_minutes,
@@ -563,7 +1146,7 @@ fn break_down_by_day(_entries: &[TimeEntry]) -> Vec<BreakdownGroup> {
 
 fn break_down_by_day_with_dates(
     entries_by_date: &std::collections::HashMap<NaiveDate, Vec<TimeEntry>>,
-    include_details: bool,
+    include_details: Option<TaskDetails>,
 ) -> Vec<BreakdownGroup> {
     let mut sorted_dates: Vec<_> = entries_by_date.keys().collect();
     sorted_dates.sort();
@@ -584,7 +1167,7 @@ fn break_down_by_day_with_dates(
 
 fn break_down_by_week_with_entries(
     entries_by_date: &std::collections::HashMap<NaiveDate, Vec<TimeEntry>>,
-    include_details: bool,
+    include_details: Option<TaskDetails>,
 ) -> Vec<BreakdownGroup> {
     let mut weeks_map: std::collections::BTreeMap<
         (i32, u32),
@@ -618,14 +1201,14 @@ fn break_down_by_week_with_entries(
         .collect()
 }
 
+type WeeksInMonth = std::collections::BTreeMap<(i32, u32), Vec<TimeEntry>>;
+
 fn break_down_by_month_with_entries(
     entries_by_date: &std::collections::HashMap<NaiveDate, Vec<TimeEntry>>,
-    include_details: bool,
+    include_details: Option<TaskDetails>,
 ) -> Vec<BreakdownGroup> {
-    let mut months_map: std::collections::BTreeMap<
-        (i32, u32),
-        std::collections::BTreeMap<(i32, u32), Vec<TimeEntry>>,
-    > = std::collections::BTreeMap::new();
+    let mut months_map: std::collections::BTreeMap<(i32, u32), WeeksInMonth> =
+        std::collections::BTreeMap::new();
 
     for (&date, entries) in entries_by_date {
         let year = date.year();
@@ -637,7 +1220,7 @@ fn break_down_by_month_with_entries(
             .entry((year, month))
             .or_default()
             .entry(week_key)
-            .or_insert_with(Vec::new)
+            .or_default()
             .extend(entries.clone());
     }
 
@@ -663,7 +1246,7 @@ fn break_down_by_month_with_entries(
 
 fn break_down_by_year_with_entries(
     entries_by_date: &std::collections::HashMap<NaiveDate, Vec<TimeEntry>>,
-    include_details: bool,
+    include_details: Option<TaskDetails>,
 ) -> Vec<BreakdownGroup> {
     let mut years_map: std::collections::BTreeMap<i32, std::collections::BTreeMap<u32, Vec<TimeEntry>>> =
         std::collections::BTreeMap::new();
@@ -675,7 +1258,7 @@ fn break_down_by_year_with_entries(
             .entry(year)
             .or_default()
             .entry(month)
-            .or_insert_with(Vec::new)
+            .or_default()
             .extend(entries.clone());
     }
 
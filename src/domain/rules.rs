@@ -0,0 +1,206 @@
+//! Sanity rules over the tracked days, e.g. a day totalling 26 hours or the same entry
+//! pasted twice; what they find is reported like a parse warning, at the entry's line.
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use super::{ParseError, TimeEntry};
+
+/// A check of the entries of one day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// The entries of a day add up to more minutes than this.
+    MaxDayMinutes(u32),
+    /// A single entry takes more minutes than this.
+    MaxEntryMinutes(u32),
+    /// An entry is the same as an earlier one of the day, on another line.
+    Duplicates,
+    /// Time is tracked on a Saturday or Sunday.
+    Weekends,
+    /// Time is tracked on a day after today.
+    FutureDates,
+}
+
+impl Rule {
+    fn check(self, date: NaiveDate, entries: &[TimeEntry], today: NaiveDate) -> Vec<ParseError> {
+        let Some(first) = entries.first() else {
+            return Vec::new();
+        };
+        match self {
+            Rule::MaxDayMinutes(max) => {
+                let total: u32 = entries.iter().map(|entry| entry.minutes).sum();
+                (total > max)
+                    .then(|| {
+                        let finding =
+                            format!("{date} totals {}, more than {}", hours(total), hours(max));
+                        at(first, ParseError::DayTooLong(finding))
+                    })
+                    .into_iter()
+                    .collect()
+            }
+            Rule::MaxEntryMinutes(max) => entries
+                .iter()
+                .filter(|entry| entry.minutes > max)
+                .map(|entry| {
+                    let finding = format!("{}, more than {}", hours(entry.minutes), hours(max));
+                    at(entry, ParseError::EntryTooLong(finding))
+                })
+                .collect(),
+            Rule::Duplicates => entries
+                .iter()
+                .enumerate()
+                .filter_map(|(index, entry)| {
+                    let original = entries[..index]
+                        .iter()
+                        .find(|earlier| is_duplicate(earlier, entry))?;
+                    let finding = match (&original.source, &entry.source) {
+                        (Some(original), Some(source)) if original.file == source.file => {
+                            format!("same as line {}", original.line)
+                        }
+                        (Some(original), _) => format!("same as {original}"),
+                        (None, _) => format!("same as an earlier entry on {date}"),
+                    };
+                    Some(at(entry, ParseError::DuplicateEntry(finding)))
+                })
+                .collect(),
+            Rule::Weekends => matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+                .then(|| {
+                    let finding = format!("{date} is {}", date.weekday());
+                    at(first, ParseError::WeekendWork(finding))
+                })
+                .into_iter()
+                .collect(),
+            Rule::FutureDates => (date > today)
+                .then(|| at(first, ParseError::FutureDate(date.to_string())))
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// Applies the rules to every day, in date order.
+#[must_use]
+pub fn check(
+    rules: &[Rule],
+    entries_by_date: &HashMap<NaiveDate, Vec<TimeEntry>>,
+    today: NaiveDate,
+) -> Vec<ParseError> {
+    let mut dates: Vec<&NaiveDate> = entries_by_date.keys().collect();
+    dates.sort();
+    dates
+        .into_iter()
+        .flat_map(|date| {
+            rules
+                .iter()
+                .flat_map(|rule| rule.check(*date, &entries_by_date[date], today))
+        })
+        .collect()
+}
+
+/// Whether two entries on different lines are the same apart from the line they are on.
+fn is_duplicate(earlier: &TimeEntry, entry: &TimeEntry) -> bool {
+    let without_source = |entry: &TimeEntry| TimeEntry {
+        source: None,
+        ..entry.clone()
+    };
+    earlier.source != entry.source && without_source(earlier) == without_source(entry)
+}
+
+/// The finding at the entry's line, when known.
+fn at(entry: &TimeEntry, error: ParseError) -> ParseError {
+    match &entry.source {
+        Some(location) => ParseError::Located {
+            error: Box::new(error),
+            location: location.clone(),
+            span: None,
+        },
+        None => error,
+    }
+}
+
+/// Minutes as they would be written, e.g. `26h` or `7h30m`.
+fn hours(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes}m"),
+        (hours, 0) => format!("{hours}h"),
+        (hours, minutes) => format!("{hours}h{minutes}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::config::ParseConfig;
+    use crate::domain::Location;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, day).unwrap()
+    }
+
+    fn entry(line: &str, line_number: usize) -> TimeEntry {
+        TimeEntry::parse_parent(line, &ParseConfig::default())
+            .unwrap()
+            .unwrap()
+            .entry
+            .written_at(
+                date(15),
+                Location {
+                    file: "test.md".to_string(),
+                    line: line_number,
+                },
+            )
+    }
+
+    fn findings(rule: Rule, day: u32, entries: Vec<TimeEntry>) -> Vec<String> {
+        let entries_by_date = HashMap::from([(date(day), entries)]);
+        check(&[rule], &entries_by_date, date(16))
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn max_day_should_flag_day_above_limit() {
+        let entries = vec![entry("- #a 20h", 2), entry("- #b 6h", 3)];
+
+        assert_eq!(
+            findings(Rule::MaxDayMinutes(24 * 60), 15, entries),
+            ["test.md: line 2: implausible day: 2025-01-15 totals 26h, more than 24h"]
+        );
+    }
+
+    #[test]
+    fn max_entry_should_flag_entries_above_limit() {
+        let entries = vec![entry("- #a 40h", 2), entry("- #b 6h", 3)];
+
+        assert_eq!(
+            findings(Rule::MaxEntryMinutes(16 * 60), 15, entries),
+            ["test.md: line 2: implausible entry: 40h, more than 16h"]
+        );
+    }
+
+    #[test]
+    fn duplicates_should_flag_repeated_line() {
+        let entries = vec![
+            entry("- #a 1h review", 2),
+            entry("- #a 1h other", 3),
+            entry("- #a 1h review", 4),
+        ];
+
+        assert_eq!(
+            findings(Rule::Duplicates, 15, entries),
+            ["test.md: line 4: duplicate entry: same as line 2"]
+        );
+    }
+
+    #[test]
+    fn weekends_and_future_dates_should_be_flagged_once_per_day() {
+        let entries = || vec![entry("- #a 1h", 2), entry("- #b 1h", 3)];
+
+        assert_eq!(findings(Rule::Weekends, 18, entries()).len(), 1);
+        assert!(findings(Rule::Weekends, 17, entries()).is_empty());
+        assert_eq!(findings(Rule::FutureDates, 17, entries()).len(), 1);
+        assert!(findings(Rule::FutureDates, 16, entries()).is_empty());
+    }
+}
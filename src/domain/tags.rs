@@ -1,6 +1,9 @@
 use serde::Serialize;
 use std::fmt;
 
+/// Separates the levels of a hierarchical tag, e.g. `#client/acme/meeting`.
+pub const TAG_PATH_SEPARATOR: char = '/';
+
 #[derive(Debug, PartialEq, Clone, Serialize)]
 pub enum Tag {
     Project(String),
@@ -29,6 +32,45 @@ impl Tag {
             Tag::Context(name) => name.clone(),
         }
     }
+
+    /// Whether this tag is the given tag or one of its descendants,
+    /// e.g. `client/acme/meeting` is within `client/acme`.
+    #[must_use]
+    pub fn is_within(&self, ancestor: &Tag) -> bool {
+        is_path_within(&self.raw_value(), &ancestor.raw_value())
+    }
+}
+
+/// Whether a tag path is the given ancestor path or lies below it.
+#[must_use]
+pub fn is_path_within(path: &str, ancestor: &str) -> bool {
+    path.strip_prefix(ancestor)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(TAG_PATH_SEPARATOR))
+}
+
+/// The path one level up, e.g. `client/acme` for `client/acme/meeting`.
+#[must_use]
+pub fn parent_path(path: &str) -> Option<&str> {
+    path.rsplit_once(TAG_PATH_SEPARATOR)
+        .map(|(parent, _)| parent)
+}
+
+/// The last level of a tag path, e.g. `meeting` for `client/acme/meeting`.
+#[must_use]
+pub fn path_leaf(path: &str) -> &str {
+    path.rsplit_once(TAG_PATH_SEPARATOR)
+        .map_or(path, |(_, leaf)| leaf)
+}
+
+/// The ancestors of a tag path up to the given depth, from the top level down,
+/// e.g. `client`, `client/acme` for `client/acme/meeting` at depth 2.
+#[must_use]
+pub fn path_prefixes(path: &str, depth: usize) -> Vec<&str> {
+    path.match_indices(TAG_PATH_SEPARATOR)
+        .map(|(index, _)| &path[..index])
+        .chain(std::iter::once(path))
+        .take(depth)
+        .collect()
 }
 
 impl fmt::Display for Tag {
@@ -61,3 +103,37 @@ impl TagFilter {
         self.filter_tags()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("client/acme/meeting", "client/acme", true)]
+    #[case("client/acme", "client/acme", true)]
+    #[case("client/acme-corp", "client/acme", false)]
+    #[case("client", "client/acme", false)]
+    fn tag_should_be_within_ancestor_path(
+        #[case] tag: &str,
+        #[case] ancestor: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            Tag::from_raw(tag).is_within(&Tag::from_raw(ancestor)),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("client/acme/meeting", 2, vec!["client", "client/acme"])]
+    #[case("client/acme/meeting", 5, vec!["client", "client/acme", "client/acme/meeting"])]
+    #[case("dev", 1, vec!["dev"])]
+    fn path_prefixes_should_stop_at_depth(
+        #[case] path: &str,
+        #[case] depth: usize,
+        #[case] expected: Vec<&str>,
+    ) {
+        assert_eq!(path_prefixes(path, depth), expected);
+    }
+}
@@ -0,0 +1,115 @@
+//! Issue keys such as `PROJ-1234` mentioned in entry descriptions.
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+
+/// Placeholder in a ticket URL template that is replaced by the key.
+const TICKET_PLACEHOLDER: &str = "{ticket}";
+
+/// The regular expression that finds ticket keys in a description.
+///
+/// When the pattern has a capture group, the first group is the key, e.g. `#(\d+)` for
+/// GitHub issues `#123`; otherwise the whole match is. There is no default pattern: one
+/// generic enough for every tracker also takes words such as `UTF-8` or `ISO-8601` for keys.
+#[derive(Debug, Clone)]
+pub struct TicketPattern(Regex);
+
+impl TicketPattern {
+    /// Distinct keys in the text, in order of appearance.
+    #[must_use]
+    pub fn find_in(&self, text: &str) -> Vec<String> {
+        let mut keys: Vec<String> = Vec::new();
+        for captures in self.0.captures_iter(text) {
+            let key = captures
+                .get(1)
+                .or_else(|| captures.get(0))
+                .map_or("", |key| key.as_str());
+            if !key.is_empty() && !keys.iter().any(|known| known == key) {
+                keys.push(key.to_string());
+            }
+        }
+        keys
+    }
+}
+
+impl PartialEq for TicketPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl<'de> Deserialize<'de> for TicketPattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pattern = String::deserialize(deserializer)?;
+        Regex::new(&pattern)
+            .map(TicketPattern)
+            .map_err(|err| serde::de::Error::custom(format!("invalid ticket pattern: {err}")))
+    }
+}
+
+/// A link template for ticket keys, e.g. `https://jira.example.com/browse/{ticket}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TicketUrl(String);
+
+impl TicketUrl {
+    /// The link to the given ticket.
+    #[must_use]
+    pub fn link(&self, key: &str) -> String {
+        self.0.replace(TICKET_PLACEHOLDER, key)
+    }
+}
+
+impl<'de> Deserialize<'de> for TicketUrl {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let template = String::deserialize(deserializer)?;
+        if template.contains(TICKET_PLACEHOLDER) {
+            Ok(TicketUrl(template))
+        } else {
+            Err(serde::de::Error::custom(format!(
+                "ticket URL should contain {TICKET_PLACEHOLDER}: {template}"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const JIRA_PATTERN: &str = r"\b[A-Z][A-Z0-9]+-\d+\b";
+
+    fn pattern(pattern: &str) -> TicketPattern {
+        TicketPattern(Regex::new(pattern).unwrap())
+    }
+
+    #[rstest]
+    #[case("PROJ-1234 fix login", &["PROJ-1234"])]
+    #[case("review of AB2-7 and PROJ-1, again AB2-7", &["AB2-7", "PROJ-1"])]
+    #[case("fix login (PROJ-1234)", &["PROJ-1234"])]
+    #[case("e-2 and proj-12 and X-1", &[])]
+    fn jira_pattern_should_find_jira_keys(#[case] text: &str, #[case] expected: &[&str]) {
+        assert_eq!(pattern(JIRA_PATTERN).find_in(text), expected);
+    }
+
+    #[test]
+    fn pattern_should_use_first_capture_group_as_key() {
+        assert_eq!(
+            pattern(r"#(\d+)").find_in("fix #123 and #45"),
+            ["123", "45"]
+        );
+    }
+
+    #[test]
+    fn url_should_replace_placeholder_with_key() {
+        let url = TicketUrl("https://jira.example.com/browse/{ticket}".to_string());
+
+        assert_eq!(url.link("PROJ-1"), "https://jira.example.com/browse/PROJ-1");
+    }
+}
@@ -1,4 +1,10 @@
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveTime, Timelike};
+use regex::Regex;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::sync::LazyLock;
+
+use super::ParseError;
 
 #[derive(Clone, Debug)]
 pub enum Clock {
@@ -26,3 +32,102 @@ impl Clock {
         }
     }
 }
+
+static TIME_RANGE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d{1,2}):(\d{2})-(\d{1,2}):(\d{2})$").unwrap());
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// Wall clock interval an entry was worked in, e.g. `09:15-10:45`.
+///
+/// An end before the start is read as crossing midnight: `23:30-00:15` lasts 45 minutes.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TimeRange {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl TimeRange {
+    /// Parses a `HH:MM-HH:MM` token.
+    ///
+    /// Returns `None` when the token is not shaped like a clock range at all.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::InvalidTime` when the token looks like a clock range but one of
+    /// its times does not exist (e.g. `25:00`) or start and end are equal.
+    pub fn parse(input: &str) -> Option<Result<Self, ParseError>> {
+        let captures = TIME_RANGE_REGEX.captures(input)?;
+        let time_at = |hour: usize, minute: usize| {
+            let hour = captures[hour].parse::<u32>().ok()?;
+            let minute = captures[minute].parse::<u32>().ok()?;
+            NaiveTime::from_hms_opt(hour, minute, 0)
+        };
+
+        let range = match (time_at(1, 2), time_at(3, 4)) {
+            (Some(start), Some(end)) if start != end => Ok(TimeRange { start, end }),
+            _ => Err(ParseError::InvalidTime(input.to_string())),
+        };
+        Some(range)
+    }
+
+    #[must_use]
+    pub fn start(&self) -> NaiveTime {
+        self.start
+    }
+
+    #[must_use]
+    pub fn end(&self) -> NaiveTime {
+        self.end
+    }
+
+    #[must_use]
+    pub fn minutes(&self) -> u32 {
+        let (start, end) = self.minutes_of_day();
+        end - start
+    }
+
+    /// Whether both ranges claim the same minute of a day.
+    ///
+    /// A range crossing midnight also claims the start of the day, so `23:00-01:00`
+    /// overlaps `00:30-02:00`.
+    #[must_use]
+    pub fn overlaps(&self, other: &TimeRange) -> bool {
+        let (start, end) = self.minutes_of_day();
+        let (other_start, other_end) = other.minutes_of_day();
+        let overlaps_shifted_by = |shift: i64| {
+            i64::from(start) < i64::from(other_end) + shift
+                && i64::from(other_start) + shift < i64::from(end)
+        };
+        let day = i64::from(MINUTES_PER_DAY);
+        [-day, 0, day].into_iter().any(overlaps_shifted_by)
+    }
+
+    /// Start and end as minutes since midnight of the day the range starts on.
+    fn minutes_of_day(&self) -> (u32, u32) {
+        let start = self.start.hour() * 60 + self.start.minute();
+        let end = self.end.hour() * 60 + self.end.minute();
+        if end > start {
+            (start, end)
+        } else {
+            (start, end + MINUTES_PER_DAY)
+        }
+    }
+}
+
+impl fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+impl Serialize for TimeRange {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
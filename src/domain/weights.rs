@@ -0,0 +1,140 @@
+//! Weights splitting one entry's time across tags or outcomes, e.g. `##launch:70% ##hiring:30%`
+//! or `#frontend*2 #backend*1`.
+
+use serde::Serialize;
+use std::fmt;
+
+/// The part of an entry's time a tag or outcome gets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub(crate) enum Weight {
+    /// `:70%`, all percentages of an entry add up to 100.
+    Percent(u32),
+    /// `*2`, time is divided in proportion to the shares.
+    Share(u32),
+}
+
+impl Weight {
+    pub(crate) fn value(self) -> u32 {
+        match self {
+            Weight::Percent(value) | Weight::Share(value) => value,
+        }
+    }
+}
+
+/// The weight as written after the name, e.g. `:70%` or `*2`.
+impl fmt::Display for Weight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Weight::Percent(percent) => write!(f, ":{percent}%"),
+            Weight::Share(share) => write!(f, "*{share}"),
+        }
+    }
+}
+
+/// Splits a trailing `:70%` or `*2` weight off a tag or outcome, e.g. `launch:70%`.
+///
+/// A suffix that is not a positive number stays part of the name, as does a number after
+/// a colon without `%`, so tags such as `#sprint:12` keep their name.
+pub(crate) fn split_weight(raw: &str) -> (&str, Option<Weight>) {
+    let weight = if let Some((name, share)) = raw.rsplit_once('*') {
+        share.parse().ok().map(|share| (name, Weight::Share(share)))
+    } else if let Some((name, percent)) = raw.rsplit_once(':') {
+        percent
+            .strip_suffix('%')
+            .and_then(|percent| percent.parse().ok())
+            .map(|percent| (name, Weight::Percent(percent)))
+    } else {
+        None
+    };
+    match weight {
+        Some((name, weight)) if weight.value() > 0 && !name.is_empty() => (name, Some(weight)),
+        _ => (raw, None),
+    }
+}
+
+/// The shares of the weights, or `None` when percentages and shares are mixed
+/// or the percentages do not add up to 100.
+pub(crate) fn shares(weights: &[Weight]) -> Option<Vec<u32>> {
+    let percentages = weights
+        .iter()
+        .filter(|weight| matches!(weight, Weight::Percent(_)))
+        .count();
+    let valid = if percentages == 0 {
+        true
+    } else {
+        percentages == weights.len()
+            && weights.iter().map(|weight| weight.value()).sum::<u32>() == 100
+    };
+    valid.then(|| weights.iter().map(|weight| weight.value()).collect())
+}
+
+/// Divides minutes in proportion to the shares, in whole minutes that add up to the total.
+///
+/// Minutes left after rounding down go to the largest remainders, the earliest share first on ties.
+pub(crate) fn apportion(minutes: u32, shares: &[u32]) -> Vec<u32> {
+    let total_shares: u64 = shares.iter().map(|&share| u64::from(share)).sum();
+    if total_shares == 0 {
+        return vec![0; shares.len()];
+    }
+    let exact: Vec<u64> = shares
+        .iter()
+        .map(|&share| u64::from(minutes) * u64::from(share))
+        .collect();
+    let mut apportioned: Vec<u32> = exact
+        .iter()
+        .map(|&value| u32::try_from(value / total_shares).expect("part of minutes fits in u32"))
+        .collect();
+
+    let left = minutes - apportioned.iter().sum::<u32>();
+    let mut by_remainder: Vec<usize> = (0..shares.len()).collect();
+    by_remainder.sort_by_key(|&index| std::cmp::Reverse(exact[index] % total_shares));
+    for &index in by_remainder.iter().take(left as usize) {
+        apportioned[index] += 1;
+    }
+    apportioned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("launch:70%", ("launch", Some(Weight::Percent(70))))]
+    #[case("frontend*2", ("frontend", Some(Weight::Share(2))))]
+    #[case("sprint:12", ("sprint:12", None))]
+    #[case("launch", ("launch", None))]
+    #[case("launch:0%", ("launch:0%", None))]
+    #[case("launch:x", ("launch:x", None))]
+    #[case("launch*0", ("launch*0", None))]
+    #[case(":50%", (":50%", None))]
+    fn split_weight_should_read_trailing_weight(
+        #[case] raw: &str,
+        #[case] expected: (&str, Option<Weight>),
+    ) {
+        assert_eq!(split_weight(raw), expected);
+    }
+
+    #[test]
+    fn shares_should_reject_percentages_not_adding_up_to_100() {
+        assert_eq!(shares(&[Weight::Percent(70), Weight::Percent(20)]), None);
+        assert_eq!(shares(&[Weight::Percent(70), Weight::Share(1)]), None);
+        assert_eq!(
+            shares(&[Weight::Percent(70), Weight::Percent(30)]),
+            Some(vec![70, 30])
+        );
+    }
+
+    #[rstest]
+    #[case(60, &[70, 30], &[42, 18])]
+    #[case(60, &[1, 1, 1], &[20, 20, 20])]
+    #[case(10, &[1, 1, 1], &[4, 3, 3])]
+    #[case(5, &[1, 2], &[2, 3])]
+    fn apportion_should_add_up_to_total(
+        #[case] minutes: u32,
+        #[case] shares: &[u32],
+        #[case] expected: &[u32],
+    ) {
+        assert_eq!(apportion(minutes, shares), expected);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod cli;
+pub mod config;
 pub mod parsing;
 mod reporting;
 
@@ -7,18 +8,41 @@ pub mod domain;
 #[cfg(feature = "web")]
 pub mod web;
 
+use domain::config::{DiagnosticsConfig, UnitDefinition};
+use domain::diagnostics::{Diagnostic, DiagnosticFormat};
 use domain::reporting::OverviewReport;
-use domain::reporting::{BreakdownUnit, OutputLimit};
+use domain::reporting::{BreakdownReport, BreakdownUnit, GroupBy, OutputLimit};
 use domain::tags::Tag;
 use domain::tags::TagFilter;
+use domain::time::Clock;
 use reporting::FormatableReport;
 
+use crate::config::Config;
 use crate::domain::ParseError;
 use crate::domain::PeriodRequested;
 use crate::parsing::filter::Filter;
 use crate::reporting::format::Formatter;
 use std::path::Path;
 
+/// What to report and how, as chosen on the command line
+pub struct RunOptions<'a> {
+    pub include_details: bool,
+    pub tag_filter: Option<&'a TagFilter>,
+    pub exclude_tags: &'a [String],
+    pub metadata: &'a [(String, String)],
+    pub person: Option<&'a str>,
+    pub exclude_leave: bool,
+    pub period: Option<&'a PeriodRequested>,
+    pub limit: Option<&'a OutputLimit>,
+    pub formatter: &'a dyn Formatter,
+    pub breakdown_unit: Option<BreakdownUnit>,
+    pub depth: Option<usize>,
+    pub group_by: Option<&'a GroupBy>,
+    pub estimates: bool,
+    pub log: bool,
+    pub diagnostics: DiagnosticFormat,
+}
+
 /// Run the time tracking report generation
 ///
 /// # Errors
@@ -29,84 +53,235 @@ use std::path::Path;
 /// - The input contains invalid time formats
 /// - The input contains invalid line formats
 /// - The requested period is invalid
-#[allow(clippy::too_many_arguments)]
+/// - There are problems in the input while in strict mode, which are printed instead of the report
 pub fn run(
     input_path: &Path,
-    include_details: bool,
-    tag_filter: Option<&TagFilter>,
-    exclude_tags: &[String],
-    period: Option<&PeriodRequested>,
-    limit: Option<&OutputLimit>,
-    formatter: &dyn Formatter,
-    breakdown_unit: Option<BreakdownUnit>,
+    options: &RunOptions,
+    clock: &Clock,
+    config: &Config,
 ) -> Result<(), ParseError> {
-    let tracking_result = process_inputs(input_path, tag_filter, exclude_tags, period)?;
+    let filter = create_filter(
+        options.tag_filter,
+        options.exclude_tags,
+        options.metadata,
+        options.person,
+        options.period,
+    );
+    let mut tracking_result =
+        parsing::process_input_with_config(input_path, None, &config.parsing)?;
+    // The rules judge whole days, so they see the entries the filter leaves out as well.
+    let findings = rule_findings(&tracking_result, clock, config);
+    tracking_result.errors.extend(findings);
+    if let Some(filter) = &filter {
+        tracking_result.time_entries = tracking_result
+            .time_entries
+            .as_ref()
+            .and_then(|time_entries| filter.apply(time_entries));
+    }
+    if config.diagnostics.strict {
+        let problems = reported_diagnostics(&tracking_result.errors, &config.diagnostics);
+        if !problems.is_empty() {
+            for problem in &problems {
+                eprintln!("{}", options.diagnostics.format(problem));
+            }
+            if options.diagnostics == DiagnosticFormat::Text {
+                eprintln!("{}", check_summary(&problems));
+            }
+            return Err(ParseError::StrictProblems(problems.len()));
+        }
+    }
+    if options.exclude_leave {
+        tracking_result.time_entries = tracking_result
+            .time_entries
+            .as_ref()
+            .and_then(domain::TrackedTime::excluding_leave);
+    }
 
-    let contexts_requested: Vec<Tag> = tag_filter
+    let contexts_requested: Vec<Tag> = options
+        .tag_filter
         .map(domain::tags::TagFilter::tags)
         .unwrap_or_default();
     print_result(
-        period,
-        limit,
-        include_details,
+        options,
         &contexts_requested,
         &tracking_result,
-        formatter,
-        breakdown_unit,
+        &config.parsing.unit_definitions(),
+    );
+    print_warnings(
+        &tracking_result.errors,
+        options.diagnostics,
+        &config.diagnostics,
     );
-    print_warnings(&tracking_result.errors);
 
     Ok(())
 }
 
-fn process_inputs(
+/// Check every file under the input path and print its problems as diagnostics
+///
+/// Returns the diagnostics found that are not suppressed, so the caller can fail when there
+/// are errors among them. As JSON, each diagnostic is a line of its own and no summary is printed.
+///
+/// # Errors
+///
+/// Returns `ParseError` if the input path cannot be read
+pub fn check(
     input_path: &Path,
-    tags_filter: Option<&TagFilter>,
-    exclude_tags: &[String],
-    period: Option<&PeriodRequested>,
-) -> Result<domain::TimeTrackingResult, ParseError> {
-    let filter = create_filter(tags_filter, exclude_tags, period);
-    let tracking_result = parsing::process_input(input_path, filter.as_ref())?;
-    Ok(tracking_result)
+    format: DiagnosticFormat,
+    clock: &Clock,
+    config: &Config,
+) -> Result<Vec<Diagnostic>, ParseError> {
+    let mut tracking_result =
+        parsing::process_input_with_config(input_path, None, &config.parsing)?;
+    let findings = rule_findings(&tracking_result, clock, config);
+    tracking_result.errors.extend(findings);
+    let diagnostics = reported_diagnostics(&tracking_result.errors, &config.diagnostics);
+    for diagnostic in &diagnostics {
+        println!("{}", format.format(diagnostic));
+    }
+    if format == DiagnosticFormat::Text {
+        println!("{}", check_summary(&diagnostics));
+    }
+    Ok(diagnostics)
+}
+
+/// What the sanity rules find in the tracked days.
+fn rule_findings(
+    tracking_result: &domain::TimeTrackingResult,
+    clock: &Clock,
+    config: &Config,
+) -> Vec<ParseError> {
+    tracking_result
+        .time_entries
+        .as_ref()
+        .map(|time_entries| {
+            domain::rules::check(
+                &config.rules.rules(),
+                &time_entries.entries_by_date,
+                clock.today(),
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// Diagnostics of the parse errors that are not suppressed, by file and line.
+fn reported_diagnostics(
+    parse_errors: &[ParseError],
+    config: &DiagnosticsConfig,
+) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = parse_errors
+        .iter()
+        .map(Diagnostic::from)
+        .filter(|diagnostic| config.reports(diagnostic))
+        .collect();
+    diagnostics.sort_by_key(|diagnostic| {
+        diagnostic
+            .location
+            .as_ref()
+            .map(|location| (location.file.clone(), location.line))
+    });
+    diagnostics
+}
+
+fn check_summary(diagnostics: &[Diagnostic]) -> String {
+    let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    let warnings = diagnostics.len() - errors;
+    match (errors, warnings) {
+        (0, 0) => "No problems found".to_string(),
+        (errors, warnings) => format!(
+            "{errors} {}, {warnings} {}",
+            if errors == 1 { "error" } else { "errors" },
+            if warnings == 1 { "warning" } else { "warnings" }
+        ),
+    }
 }
 
 fn print_result(
-    period: Option<&PeriodRequested>,
-    limit: Option<&OutputLimit>,
-    include_details: bool,
+    options: &RunOptions,
     project: &[Tag],
     tracking_result: &domain::TimeTrackingResult,
-    formatter: &dyn Formatter,
-    breakdown_unit: Option<BreakdownUnit>,
+    units: &[UnitDefinition],
 ) {
+    let RunOptions {
+        include_details,
+        period,
+        limit,
+        formatter,
+        breakdown_unit,
+        depth,
+        group_by,
+        estimates,
+        log,
+        ..
+    } = *options;
     if let Some(ref time_report) = tracking_result.time_entries {
         if let Some(unit) = breakdown_unit {
-            let report = domain::reporting::BreakdownReport::from_tracked_time(time_report, unit, include_details);
+            let report = match depth {
+                Some(depth) => {
+                    BreakdownReport::from_tracked_time_at_depth(time_report, unit, depth)
+                }
+                None => BreakdownReport::from_tracked_time(time_report, unit, include_details),
+            };
             let report = FormatableReport::BreakdownReport(&report);
             println!("{}", formatter.format(&report));
+        } else if log {
+            let report = time_report.log();
+            let report = FormatableReport::LogReport(&report);
+            println!("{}", formatter.format(&report));
+        } else if estimates {
+            let report = time_report.estimates_tracked();
+            let report = FormatableReport::EstimateReport(&report);
+            println!("{}", formatter.format(&report));
         } else if include_details {
-            let report = time_report.tasks_tracked_for(project);
+            let report = match group_by {
+                Some(key) => time_report.tasks_tracked_by(key),
+                None => time_report.tasks_tracked_for(project),
+            };
             let report = FormatableReport::TasksReport(&report);
             println!("{}", formatter.format(&report));
         } else {
-            let overview = OverviewReport::overview(time_report, limit, period);
+            let overview = match (group_by, depth) {
+                (Some(key), _) => {
+                    OverviewReport::overview_grouped_by(time_report, limit, period, key)
+                }
+                (None, Some(depth)) => {
+                    OverviewReport::overview_at_depth(time_report, limit, period, depth)
+                }
+                (None, None) => OverviewReport::overview(time_report, limit, period),
+            };
             let report = FormatableReport::OverviewReport(&overview);
             println!("{}", formatter.format(&report));
         }
+        let units_note = formatter.format_units(units);
+        if !units.is_empty() && !units_note.is_empty() {
+            println!("{units_note}");
+        }
     } else {
         println!("No data found.");
     }
 }
 
-fn print_warnings(parse_errors: &[ParseError]) {
+fn print_warnings(
+    parse_errors: &[ParseError],
+    format: DiagnosticFormat,
+    config: &DiagnosticsConfig,
+) {
     for error in parse_errors {
-        eprintln!("Warning: {error}");
+        let diagnostic = Diagnostic::from(error);
+        if !config.reports(&diagnostic) {
+            continue;
+        }
+        match format {
+            DiagnosticFormat::Text => eprintln!("Warning: {error}"),
+            DiagnosticFormat::Json => eprintln!("{}", format.format(&diagnostic)),
+        }
     }
 }
 
 fn create_filter(
     tags_filter: Option<&TagFilter>,
     exclude_tags: &[String],
+    metadata: &[(String, String)],
+    person: Option<&str>,
     period: Option<&PeriodRequested>,
 ) -> Option<Filter> {
     let period_filter = period
@@ -114,10 +289,17 @@ fn create_filter(
         .map(|period| Filter::DateRange(period.date_range()));
     let tags_filter = tags_filter.map(|filter| Filter::Tags(filter.filter_tags()));
     let exclude_tag_filter = Filter::ExcludeTags(exclude_tags.to_vec());
+    let metadata_filters = metadata.iter().map(|(key, value)| Filter::Metadata {
+        key: key.clone(),
+        value: value.clone(),
+    });
+    let person_filter = person.map(|name| Filter::Person(name.to_string()));
 
     tags_filter
         .into_iter()
         .chain(period_filter)
+        .chain(metadata_filters)
+        .chain(person_filter)
         .chain(Some(exclude_tag_filter))
         .reduce(Filter::combine)
 }
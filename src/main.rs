@@ -1,8 +1,9 @@
 use anyhow::Result;
 use chrono::NaiveDate;
-use time_tracker::cli::Args;
+use time_tracker::cli::{Args, Command};
 use time_tracker::cli::statistics::{StatisticsCollector, write_stat_record};
 use time_tracker::domain::time::Clock;
+use time_tracker::RunOptions;
 
 #[cfg(feature = "web")]
 use std::env;
@@ -53,23 +54,50 @@ fn run_cli(args: Args) -> Result<()> {
         println!("Processing path: {}", input.display());
     }
 
+    if args.command == Some(Command::Check) {
+        let config = args.config()?;
+        let diagnostics = time_tracker::check(input, args.diagnostic_format(), &clock, &config)?;
+        if diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.is_error() || config.diagnostics.strict)
+        {
+            anyhow::bail!("check found errors");
+        }
+        return Ok(());
+    }
+
     let filter = args.context_filter();
     let exclude_tags = args.exclude_tags();
+    let metadata = args.metadata_filter();
     let period = args.period(&clock)?;
     let formatter = args.formatter();
     let breakdown_unit = args.breakdown_unit(period.as_ref());
-
-    time_tracker::run(
-        input,
-        args.include_details(),
-        filter.as_ref(),
-        &exclude_tags,
-        period.as_ref(),
-        args.limit().as_ref(),
-        &*formatter,
+    let config = args.config()?;
+    if args.by_ticket && config.parsing.ticket_pattern.is_none() {
+        anyhow::bail!("--by-ticket needs a \"ticket-pattern\" in the parsing settings");
+    }
+    let grouping = args.grouping(&config.reporting);
+    let limit = args.limit();
+
+    let options = RunOptions {
+        include_details: args.include_details(),
+        tag_filter: filter.as_ref(),
+        exclude_tags: &exclude_tags,
+        metadata: &metadata,
+        person: args.person_filter(),
+        exclude_leave: args.exclude_leave,
+        period: period.as_ref(),
+        limit: limit.as_ref(),
+        formatter: &*formatter,
         breakdown_unit,
-    )
-    .map_err(anyhow::Error::from)?;
+        depth: args.depth,
+        group_by: grouping.as_ref(),
+        estimates: args.estimates,
+        log: args.log,
+        diagnostics: args.diagnostic_format(),
+    };
+
+    time_tracker::run(input, &options, &clock, &config).map_err(anyhow::Error::from)?;
     Ok(())
 }
 
@@ -77,6 +105,7 @@ fn run_cli(args: Args) -> Result<()> {
 fn run_web_server(args: Args) -> Result<()> {
     use time_tracker::web::{self, AppState};
 
+    let config = args.config()?;
     let rt = tokio::runtime::Runtime::new()?;
     rt.block_on(async {
         let data_path = if let Some(path) = args.input {
@@ -110,6 +139,7 @@ fn run_web_server(args: Args) -> Result<()> {
         let state = Arc::new(AppState {
             data_path,
             clock: web::handlers::create_clock(),
+            config,
         });
         let app = web::server::create_router_with_state(state);
 
@@ -0,0 +1,99 @@
+use crate::domain::config::{ParseConfig, TimeMarker};
+use crate::domain::ParseError;
+use std::ops::Range;
+
+/// Extracts the settings of a per-file directive, e.g. `<!-- tt: time-marker = first -->`.
+pub(crate) fn maybe_directive(line: &str) -> Option<&str> {
+    directive_range(line).map(|range| &line[range])
+}
+
+/// The byte range of a directive's settings in its line.
+pub(crate) fn directive_range(line: &str) -> Option<Range<usize>> {
+    let inner = line.strip_prefix("<!--")?.strip_suffix("-->")?;
+    let after_marker = inner.trim_start().strip_prefix("tt:")?;
+    let settings = after_marker.trim();
+    let start = "<!--".len()
+        + (inner.len() - inner.trim_start().len())
+        + "tt:".len()
+        + (after_marker.len() - after_marker.trim_start().len());
+    Some(start..start + settings.len())
+}
+
+/// Applies a directive's `key = value` setting to the configuration used for the rest of the file.
+pub(crate) fn apply_directive(directive: &str, config: &mut ParseConfig) -> Result<(), ParseError> {
+    let invalid = || ParseError::InvalidDirective(directive.to_string());
+    let (key, value) = directive.split_once('=').ok_or_else(invalid)?;
+    match key.trim() {
+        "time-marker" => {
+            config.time_marker = TimeMarker::from_name(value.trim()).ok_or_else(invalid)?;
+        }
+        "metadata-keys" => {
+            config.metadata_keys = value
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(ToString::to_string)
+                .collect();
+        }
+        _ => return Err(invalid()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("<!-- tt: time-marker = first -->", Some("time-marker = first"))]
+    #[case("<!--tt:time-marker=sigil-->", Some("time-marker=sigil"))]
+    #[case("<!-- a regular comment -->", None)]
+    #[case("- #tag 1h", None)]
+    fn maybe_directive_should_extract_settings(#[case] line: &str, #[case] expected: Option<&str>) {
+        assert_eq!(maybe_directive(line), expected);
+    }
+
+    #[test]
+    fn directive_range_should_cover_settings() {
+        let line = "<!--  tt:  time-marker = first -->";
+
+        assert_eq!(
+            directive_range(line).map(|range| &line[range]),
+            Some("time-marker = first")
+        );
+    }
+
+    #[test]
+    fn apply_directive_should_set_time_marker() {
+        let mut config = ParseConfig::default();
+
+        apply_directive("time-marker = sigil", &mut config).unwrap();
+
+        assert_eq!(config.time_marker, TimeMarker::Sigil);
+    }
+
+    #[test]
+    fn apply_directive_should_set_metadata_keys() {
+        let mut config = ParseConfig::default();
+
+        apply_directive("metadata-keys = client, billable", &mut config).unwrap();
+
+        assert_eq!(config.metadata_keys, ["client", "billable"]);
+    }
+
+    #[rstest]
+    #[case("time-marker")]
+    #[case("time-marker = sometimes")]
+    #[case("unknown = first")]
+    fn apply_directive_should_fail_when_invalid(#[case] directive: &str) {
+        let mut config = ParseConfig::default();
+
+        let result = apply_directive(directive, &mut config);
+
+        assert_eq!(
+            result,
+            Err(ParseError::InvalidDirective(directive.to_string()))
+        );
+    }
+}
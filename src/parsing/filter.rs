@@ -1,12 +1,23 @@
-use crate::domain::dates::EntryDate;
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use crate::domain::dates::{EndDate, EntryDate, StartDate};
 use crate::domain::tags::Tag;
-use crate::domain::{DateRange, TimeEntry};
+use crate::domain::{DateRange, TimeEntry, TrackedTime};
 
 #[derive(Debug, Clone)]
 pub enum Filter {
     Tags(Vec<Tag>),
     ExcludeTags(Vec<String>),
     DateRange(DateRange),
+    /// Entries with the given `key:value` metadata.
+    Metadata {
+        key: String,
+        value: String,
+    },
+    /// Entries mentioning the given person, e.g. `alice` for `@alice`.
+    Person(String),
     And(Box<Filter>, Box<Filter>),
 }
 
@@ -14,24 +25,84 @@ impl Filter {
     #[must_use]
     pub fn matches(&self, entry: &TimeEntry, date: &EntryDate) -> bool {
         match self {
-            Filter::Tags(tags) => tags.iter().any(|tag| entry.get_tags().contains(tag)),
+            Filter::Tags(tags) => tags.iter().any(|tag| has_tag_within(entry, tag)),
             Filter::ExcludeTags(tags) => !tags
                 .iter()
-                .any(|tag| entry.get_tags().contains(&Tag::from_raw(tag))),
+                .any(|tag| has_tag_within(entry, &Tag::from_raw(tag))),
             Filter::DateRange(date_range) => date_range.matches(date),
+            Filter::Metadata { key, value } => entry.metadata.get(key) == Some(value),
+            Filter::Person(name) => entry
+                .people
+                .iter()
+                .any(|person| person.eq_ignore_ascii_case(name)),
             Filter::And(f1, f2) => f1.matches(entry, date) && f2.matches(entry, date),
         }
     }
+    /// Whether a day belongs to the report; only date ranges select days.
+    #[must_use]
+    pub fn matches_date(&self, date: &EntryDate) -> bool {
+        match self {
+            Filter::DateRange(date_range) => date_range.matches(date),
+            Filter::And(f1, f2) => f1.matches_date(date) && f2.matches_date(date),
+            Filter::Tags(_)
+            | Filter::ExcludeTags(_)
+            | Filter::Metadata { .. }
+            | Filter::Person(_) => true,
+        }
+    }
+
+    /// The tracked time of the entries and days the filter selects, or `None` when no entries
+    /// are left.
+    #[must_use]
+    pub fn apply(&self, tracked: &TrackedTime) -> Option<TrackedTime> {
+        let entries_by_date: HashMap<NaiveDate, Vec<TimeEntry>> = tracked
+            .entries_by_date
+            .iter()
+            .map(|(date, entries)| {
+                let selected: Vec<TimeEntry> = entries
+                    .iter()
+                    .filter(|entry| self.matches(entry, &EntryDate(*date)))
+                    .cloned()
+                    .collect();
+                (*date, selected)
+            })
+            .filter(|(_, entries)| !entries.is_empty())
+            .collect();
+        let start = entries_by_date.keys().min().map(|&date| StartDate(date))?;
+        let end = entries_by_date.keys().max().map(|&date| EndDate(date))?;
+        let days = u32::try_from(entries_by_date.len()).unwrap_or(u32::MAX);
+        let entries = entries_by_date.values().flatten().cloned().collect();
+        let day_records = tracked
+            .day_records
+            .iter()
+            .filter(|(date, _)| self.matches_date(&EntryDate(**date)))
+            .map(|(date, record)| (*date, record.clone()))
+            .collect();
+        Some(
+            TrackedTime::with_entries_by_date(entries, entries_by_date, start, end, days)
+                .with_day_records(day_records),
+        )
+    }
+
     #[must_use]
     pub fn combine(self, other: Filter) -> Filter {
         Filter::And(Box::new(self), Box::new(other))
     }
 }
 
+/// Tags match on their path prefix, so `client` also selects `client/acme/meeting`.
+fn has_tag_within(entry: &TimeEntry, tag: &Tag) -> bool {
+    entry
+        .get_tags()
+        .iter()
+        .any(|entry_tag| entry_tag.is_within(tag))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::NaiveDate;
+    use crate::domain::config::ParseConfig;
+    use crate::parsing::process_content;
 
     #[test]
     fn test_week_of_date_range() {
@@ -51,4 +122,26 @@ mod tests {
             NaiveDate::from_ymd_opt(2024, 1, 21).unwrap()
         );
     }
+
+    #[test]
+    fn apply_should_keep_selected_entries_and_their_days() {
+        let content = "## TT 2025-01-15
+- #prj-1 1h Task A
+- #prj-2 2h Task B
+## TT 2025-01-16
+- #prj-2 3h Task C";
+        let tracked = process_content(content, "test.md", &ParseConfig::default())
+            .time_entries
+            .unwrap();
+
+        let filtered = Filter::Tags(vec![Tag::from_raw("prj-1")])
+            .apply(&tracked)
+            .unwrap();
+
+        assert_eq!(filtered.total_minutes, 60);
+        assert_eq!(
+            filtered.entries_by_date.keys().collect::<Vec<_>>(),
+            vec![&NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()]
+        );
+    }
 }
@@ -1,11 +1,237 @@
-pub(crate) fn maybe_date_from_header(line: &str) -> Option<&str> {
-    let mut words = line.split_whitespace();
+use chrono::{Datelike, NaiveDate, Weekday};
 
-    if matches!(words.next(), Some(first) if first.starts_with('#'))
-        && matches!(words.next(), Some("TT"))
+use crate::domain::config::TagGrammar;
+use crate::domain::days::DayRecord;
+use crate::domain::tags::Tag;
+use crate::domain::{words, Outcome, ParseError};
+
+/// The words after `TT` in a `# TT <date> [weekday] [annotations]` header, e.g.
+/// `## TT 2025-01-15 Wed`, with their byte offsets in the header.
+#[derive(Debug, PartialEq)]
+pub(crate) struct HeaderDate<'a> {
+    words: Vec<(usize, &'a str)>,
+}
+
+pub(crate) fn maybe_date_from_header(line: &str) -> Option<HeaderDate<'_>> {
+    let mut words = words(line);
+
+    if matches!(words.next(), Some((_, first)) if first.starts_with('#'))
+        && matches!(words.next(), Some((_, "TT")))
     {
-        words.next()
+        let words: Vec<(usize, &str)> = words.collect();
+        (!words.is_empty()).then_some(HeaderDate { words })
     } else {
         None
     }
 }
+
+impl HeaderDate<'_> {
+    /// Reads the date with the first matching format.
+    ///
+    /// A format matches as many words as it has, so `%d %b %Y` reads `15 Jan 2025`.
+    /// A weekday after the date, e.g. `Wed` or `(Wednesday)`, is checked against the date;
+    /// a mismatch is returned as a warning. Other words after the date are left to
+    /// [`HeaderDate::day_record`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::InvalidDate` if none of the formats matches.
+    pub(crate) fn parse(
+        &self,
+        formats: &[String],
+    ) -> Result<(NaiveDate, Option<ParseError>), ParseError> {
+        let (date, date_words) = self.matching_date(formats).ok_or_else(|| {
+            let (start, word) = self.words[0];
+            ParseError::InvalidDate(word.to_string()).in_token(start..start + word.len())
+        })?;
+
+        let warning = self
+            .words
+            .get(date_words)
+            .and_then(|&(start, word)| {
+                word.trim_matches(|c| matches!(c, '(' | ')' | ','))
+                    .parse::<Weekday>()
+                    .ok()
+                    .map(|weekday| (weekday, start..start + word.len()))
+            })
+            .filter(|(weekday, _)| *weekday != date.weekday())
+            .map(|(weekday, token)| {
+                ParseError::WeekdayMismatch(format!(
+                    "{} is {}, not {weekday}",
+                    self.date_text(date_words),
+                    date.weekday()
+                ))
+                .in_token(token)
+            });
+        Ok((date, warning))
+    }
+
+    /// Annotations about the day, e.g. `[holiday]` or `{location:home}`, with warnings
+    /// for unknown ones.
+    pub(crate) fn day_record(&self, formats: &[String]) -> (DayRecord, Vec<ParseError>) {
+        let date_words = self
+            .matching_date(formats)
+            .map_or(1, |(_, date_words)| date_words);
+        DayRecord::parse(self.words[date_words..].iter().copied())
+    }
+
+    /// The date read with the first matching format and the number of words it took.
+    fn matching_date(&self, formats: &[String]) -> Option<(NaiveDate, usize)> {
+        formats.iter().find_map(|format| {
+            let date_words = format.split_whitespace().count().max(1);
+            if date_words > self.words.len() {
+                return None;
+            }
+            NaiveDate::parse_from_str(&self.date_text(date_words), format)
+                .ok()
+                .map(|date| (date, date_words))
+        })
+    }
+
+    /// The first words, joined by single spaces, e.g. `15 Jan 2025`.
+    fn date_text(&self, date_words: usize) -> String {
+        self.words[..date_words]
+            .iter()
+            .map(|&(_, word)| word)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Markdown heading level of a header line, e.g. `3` for `### Notes`.
+pub(crate) fn header_level(line: &str) -> usize {
+    line.chars().take_while(|c| *c == '#').count()
+}
+
+/// Tags and outcome of a sub-heading inside a TT day, e.g. `### #prj-alpha ##launch`,
+/// inherited by the entries below it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct HeaderScope {
+    pub(crate) level: usize,
+    pub(crate) tags: Vec<Tag>,
+    pub(crate) outcome: Option<Outcome>,
+}
+
+impl HeaderScope {
+    pub(crate) fn parse(line: &str, grammar: &TagGrammar) -> Self {
+        let mut tags = Vec::new();
+        let mut outcome = None;
+        for word in line.split_whitespace().skip(1) {
+            if let Some(description) = word.strip_prefix("##").filter(|d| !d.is_empty()) {
+                outcome = Some(Outcome::parse(description.to_string()));
+            } else if let Some(raw_tag) = word.strip_prefix('#').filter(|t| grammar.accepts(t)) {
+                tags.push(Tag::from_raw(raw_tag));
+            }
+        }
+        Self {
+            level: header_level(line),
+            tags,
+            outcome,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::config::ParseConfig;
+    use crate::domain::days::Leave;
+    use rstest::rstest;
+
+    fn parse(
+        line: &str,
+        formats: &[String],
+    ) -> Result<(NaiveDate, Option<ParseError>), ParseError> {
+        maybe_date_from_header(line)
+            .expect("line should be a TT header")
+            .parse(formats)
+    }
+
+    fn default_formats() -> Vec<String> {
+        ParseConfig::default().date_formats
+    }
+
+    #[rstest]
+    #[case("## TT 2025-01-15")]
+    #[case("## TT 2025-01-15 Wed")]
+    #[case("## TT 2025-01-15 (Wednesday)")]
+    #[case("## TT 2025-01-15 notes")]
+    #[case("## TT 2025-01-15 [holiday]")]
+    #[case("# TT 2025-W03-3")]
+    fn header_should_parse_with_default_formats(#[case] line: &str) {
+        let expected = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        assert_eq!(parse(line, &default_formats()), Ok((expected, None)));
+    }
+
+    #[test]
+    fn header_should_parse_with_configured_format() {
+        let expected = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        assert_eq!(
+            parse("# TT 15/01/2025", &["%d/%m/%Y".to_string()]),
+            Ok((expected, None))
+        );
+    }
+
+    #[test]
+    fn header_should_parse_with_configured_format_of_several_words() {
+        let expected = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        assert_eq!(
+            parse("# TT 15 Jan 2025 Wed", &["%d %b %Y".to_string()]),
+            Ok((expected, None))
+        );
+    }
+
+    #[test]
+    fn header_should_fail_when_no_format_matches() {
+        assert_eq!(
+            parse("# TT 15/01/2025", &default_formats()),
+            Err(ParseError::InvalidDate("15/01/2025".to_string()).in_token(5..15))
+        );
+    }
+
+    #[test]
+    fn sub_heading_should_provide_tags_and_outcome() {
+        let scope = HeaderScope::parse(
+            "### #prj-alpha #design ##launch Design work",
+            &TagGrammar::default(),
+        );
+
+        assert_eq!(scope.level, 3);
+        assert_eq!(
+            scope.tags,
+            vec![Tag::from_raw("prj-alpha"), Tag::from_raw("design")]
+        );
+        assert_eq!(scope.outcome, Some(Outcome::parse("launch".to_string())));
+    }
+
+    #[test]
+    fn header_should_provide_day_annotations() {
+        let (record, warnings) =
+            maybe_date_from_header("## TT 2025-01-15 Wed [sick] {location:home}")
+                .expect("line should be a TT header")
+                .day_record(&default_formats());
+
+        assert_eq!(record.leave, Some(Leave::Sick));
+        assert_eq!(
+            record.metadata.get("location").map(String::as_str),
+            Some("home")
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn header_should_warn_when_weekday_does_not_match_date() {
+        let (_, warning) = parse("## TT 2025-01-15 Tue", &default_formats()).unwrap();
+
+        assert_eq!(
+            warning,
+            Some(
+                ParseError::WeekdayMismatch("2025-01-15 is Wed, not Tue".to_string())
+                    .in_token(17..20)
+            )
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use super::directive_parser::maybe_directive;
+
+/// Markdown block the parser is in; lines inside code fences, HTML comments
+/// and block quotes are not time tracking content.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) enum MarkdownBlock {
+    #[default]
+    Text,
+    /// Inside a code fence opened with the given marker, e.g. "```" or "~~~~".
+    Fence(String),
+    /// Inside a multi-line `<!-- -->` comment.
+    Comment,
+}
+
+impl MarkdownBlock {
+    /// Moves past a (trimmed) line and tells whether the line should be skipped.
+    ///
+    /// Single-line `<!-- tt: ... -->` directives are not skipped.
+    pub(crate) fn skips(&mut self, line: &str) -> bool {
+        match self {
+            MarkdownBlock::Fence(marker) => {
+                if closes_fence(line, marker) {
+                    *self = MarkdownBlock::Text;
+                }
+                true
+            }
+            MarkdownBlock::Comment => {
+                if line.contains("-->") {
+                    *self = MarkdownBlock::Text;
+                }
+                true
+            }
+            MarkdownBlock::Text => {
+                if let Some(marker) = fence_marker(line) {
+                    *self = MarkdownBlock::Fence(marker.to_string());
+                    true
+                } else if let Some(comment) = line.strip_prefix("<!--") {
+                    if !comment.contains("-->") {
+                        *self = MarkdownBlock::Comment;
+                    }
+                    maybe_directive(line).is_none()
+                } else {
+                    line.starts_with('>')
+                }
+            }
+        }
+    }
+}
+
+/// The run of backticks or tildes opening a code fence, at least three long.
+fn fence_marker(line: &str) -> Option<&str> {
+    ['`', '~'].into_iter().find_map(|fence_char| {
+        let length = line.chars().take_while(|&c| c == fence_char).count();
+        (length >= 3).then(|| &line[..length])
+    })
+}
+
+fn closes_fence(line: &str, marker: &str) -> bool {
+    fence_marker(line).is_some_and(|closing| {
+        closing.starts_with(marker) && line[closing.len()..].trim().is_empty()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn skipped_lines(content: &str) -> Vec<bool> {
+        let mut block = MarkdownBlock::default();
+        content
+            .lines()
+            .map(|line| block.skips(line.trim()))
+            .collect()
+    }
+
+    #[test]
+    fn fence_should_skip_until_closed() {
+        let content = "```bash\n# TT 2020-01-01\n```\n- #dev 1h";
+
+        assert_eq!(skipped_lines(content), vec![true, true, true, false]);
+    }
+
+    #[test]
+    fn fence_should_only_close_with_same_marker() {
+        let content = "````\n```\n~~~\n````\ntext";
+
+        assert_eq!(skipped_lines(content), vec![true, true, true, true, false]);
+    }
+
+    #[test]
+    fn comment_should_skip_until_closed() {
+        let content = "<!--\n- #dev 1h\n-->\n- #dev 1h";
+
+        assert_eq!(skipped_lines(content), vec![true, true, true, false]);
+    }
+
+    #[rstest]
+    #[case("<!-- - #dev 1h -->", true)]
+    #[case("<!-- tt: time-marker = first -->", false)]
+    #[case("> - #dev 1h", true)]
+    #[case("- #dev 1h", false)]
+    fn single_line_should_be_skipped(#[case] line: &str, #[case] expected: bool) {
+        assert_eq!(MarkdownBlock::default().skips(line), expected);
+    }
+}
@@ -1,15 +1,19 @@
+mod directive_parser;
 pub mod filter;
 mod header_parser;
+mod markdown_blocks;
 mod model;
 mod parser;
 mod processor;
 
 use model::ContentParseResults;
-pub(crate) use model::{LineType, ParseState, ParsedLine};
+pub(crate) use model::{LineType, ParseState, ParsedLine, PendingEntry};
 use processor::Processor;
 
+use std::collections::HashMap;
 use std::path::Path;
 
+use crate::domain::config::ParseConfig;
 use crate::domain::dates::EndDate;
 use crate::domain::dates::StartDate;
 use crate::domain::TimeTrackingResult;
@@ -22,20 +26,49 @@ use crate::domain::TrackedTime;
 pub fn process_input(
     path: &Path,
     filter: Option<&Filter>,
+) -> Result<TimeTrackingResult, ParseError> {
+    process_input_with_config(path, filter, &ParseConfig::default())
+}
+
+pub fn process_input_with_config(
+    path: &Path,
+    filter: Option<&Filter>,
+    config: &ParseConfig,
 ) -> Result<TimeTrackingResult, ParseError> {
     let processor = Processor::from_path(path);
-    let parse_result = parse_entries_from_path(path, filter, &processor)?;
+    let parse_result = parse_entries_from_path(path, filter, &processor, config)?;
     Ok(tracking_result(&parse_result))
 }
 
+/// Parses content as if read from a file named `file_name`, e.g. to fuzz the parser in-process.
+#[must_use]
+pub fn process_content(content: &str, file_name: &str, config: &ParseConfig) -> TimeTrackingResult {
+    let parse_result =
+        parser::parse_content(content, None, file_name, config, None, &mut HashMap::new());
+    tracking_result(&parse_result)
+}
+
 fn parse_entries_from_path(
     path: &Path,
     filter: Option<&Filter>,
     processor: &Processor,
+    config: &ParseConfig,
 ) -> Result<ContentParseResults, ParseError> {
     let mut parse_result = ContentParseResults::errors_only(vec![]);
+    let mut time_ranges = HashMap::new();
     processor.process(path, |input| {
-        let result = parser::parse_content(input.content(), filter, input.file_name());
+        let note_date = config
+            .daily_notes
+            .then(|| input.daily_note_date(&config.date_formats))
+            .flatten();
+        let result = parser::parse_content(
+            input.content(),
+            filter,
+            input.file_name(),
+            config,
+            note_date,
+            &mut time_ranges,
+        );
         parse_result = parse_result.merge(&result);
         Ok(())
     })?;
@@ -60,13 +93,10 @@ fn tracked_time(parse_result: &ContentParseResults) -> Option<TrackedTime> {
             let end = entries.keys().max().map(|&date| EndDate(date))?;
             let days = parse_result.days();
             let flat_entries = entries.values().flat_map(|v| v.iter().cloned()).collect();
-            Some(TrackedTime::with_entries_by_date(
-                flat_entries,
-                entries.clone(),
-                start,
-                end,
-                days,
-            ))
+            Some(
+                TrackedTime::with_entries_by_date(flat_entries, entries.clone(), start, end, days)
+                    .with_day_records(parse_result.day_records().clone()),
+            )
         })
 }
 
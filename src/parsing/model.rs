@@ -1,59 +1,263 @@
-use super::header_parser::maybe_date_from_header;
-use crate::domain::{EntryLineParseResult, ParseError, TimeEntry};
+use super::directive_parser::directive_range;
+use super::header_parser::{header_level, maybe_date_from_header, HeaderScope};
+use super::markdown_blocks::MarkdownBlock;
+use crate::domain::config::ParseConfig;
+use crate::domain::days::DayRecord;
+use crate::domain::tags::Tag;
+use crate::domain::time::TimeRange;
+use crate::domain::{Outcome, ParseError, ParsedEntry, TimeEntry};
 use chrono::NaiveDate;
 use std::collections::HashMap;
+use std::ops::Range;
 
 #[derive(Default, Clone, Debug)]
 pub(crate) struct ParseState {
     pub(crate) entries: HashMap<NaiveDate, Vec<TimeEntry>>,
     pub(crate) current_date: Option<NaiveDate>,
     pub(crate) errors: Vec<ParseError>,
+    pub(crate) time_ranges: HashMap<NaiveDate, Vec<TimeRange>>,
+    /// Parsing configuration for the current file, adjusted by its directives.
+    pub(crate) config: ParseConfig,
+    /// Code fence, comment or quote the current line is in.
+    pub(crate) block: MarkdownBlock,
+    /// The day of a daily note, used outside explicit TT sections.
+    pub(crate) note_date: Option<NaiveDate>,
+    /// Heading level of the current TT header, 0 for a daily note without one.
+    pub(crate) day_level: usize,
+    /// Sub-headings of the current day whose tags and outcome entries inherit, outermost first.
+    pub(crate) scopes: Vec<HeaderScope>,
+    /// The last entry read, kept open while its indented subtasks follow.
+    pub(crate) pending: Option<PendingEntry>,
+    /// Annotations of the TT headers, e.g. `[holiday]`, for days that have any.
+    pub(crate) day_records: HashMap<NaiveDate, DayRecord>,
+}
+
+/// An entry whose subtasks may still follow on more indented lines.
+#[derive(Clone, Debug)]
+pub(crate) struct PendingEntry {
+    pub(crate) date: NaiveDate,
+    pub(crate) entry: TimeEntry,
+    pub(crate) has_time: bool,
+    pub(crate) subtasks: Vec<TimeEntry>,
+    pub(crate) indent: usize,
+    pub(crate) line: String,
+    pub(crate) line_number: usize,
+    /// Byte offset of `line` in the line as written, i.e. the length of its leading whitespace.
+    pub(crate) offset: usize,
+    /// Byte range of the entry's clock range in `line`, if it has one.
+    pub(crate) range_token: Option<Range<usize>>,
+}
+
+impl PendingEntry {
+    /// Whether a line belongs to this entry's subtasks, i.e. is indented deeper.
+    pub(crate) fn is_parent_of(&self, line: &ParsedLine) -> bool {
+        line.indent > self.indent
+    }
 }
 
 impl ParseState {
+    pub(crate) fn with_config(config: &ParseConfig) -> Self {
+        Self {
+            config: config.clone(),
+            ..Self::default()
+        }
+    }
+
+    /// State for a daily note, whose lines belong to the note's day unless a TT header says otherwise.
+    pub(crate) fn for_daily_note(config: &ParseConfig, note_date: NaiveDate) -> Self {
+        Self {
+            current_date: Some(note_date),
+            note_date: Some(note_date),
+            ..Self::with_config(config)
+        }
+    }
+
     pub(crate) fn in_time_tracking_section(&self) -> bool {
         self.current_date.is_some()
     }
+
+    /// Starts a new day at a TT header.
+    pub(crate) fn start_day(&mut self, date: NaiveDate, level: usize, record: &DayRecord) {
+        self.current_date = Some(date);
+        self.day_level = level;
+        self.scopes.clear();
+        if !record.is_empty() {
+            self.day_records.entry(date).or_default().merge(record);
+        }
+    }
+
+    /// Annotations of the current day, if its TT header has any.
+    pub(crate) fn current_day(&self) -> Option<&DayRecord> {
+        self.current_date
+            .and_then(|date| self.day_records.get(&date))
+    }
+
+    /// Leaves the current day at a header that is not below its TT header.
+    pub(crate) fn end_day(&mut self) {
+        self.current_date = self.note_date;
+        self.day_level = 0;
+        self.scopes.clear();
+    }
+
+    /// Whether a header without date is a sub-heading of the current day.
+    pub(crate) fn is_sub_heading(&self, level: usize) -> bool {
+        self.in_time_tracking_section() && level > self.day_level
+    }
+
+    /// Enters a sub-heading, leaving sub-headings of the same or a deeper level.
+    pub(crate) fn enter_scope(&mut self, scope: HeaderScope) {
+        self.scopes.retain(|outer| outer.level < scope.level);
+        self.scopes.push(scope);
+    }
+
+    /// Tags of the enclosing sub-headings, outermost first.
+    pub(crate) fn scope_tags(&self) -> Vec<Tag> {
+        let mut tags: Vec<Tag> = Vec::new();
+        for tag in self.scopes.iter().flat_map(|scope| &scope.tags) {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        tags
+    }
+
+    /// Outcome of the innermost sub-heading that has one.
+    pub(crate) fn scope_outcome(&self) -> Option<&Outcome> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.outcome.as_ref())
+    }
+
+    /// Records the clock range of an entry on the given day.
+    ///
+    /// Ranges are tracked for every entry, also those filtered out of the report,
+    /// so overlaps are detected regardless of the filter used, and across files:
+    /// a range also overlaps one written for the same day in a file read before.
+    /// `token` is the byte range of the clock range in its line, where it was written.
+    pub(crate) fn claim_time_range(
+        &mut self,
+        date: NaiveDate,
+        range: TimeRange,
+        token: Option<Range<usize>>,
+    ) -> Result<(), ParseError> {
+        let claimed = self.time_ranges.entry(date).or_default();
+        let overlapping = claimed.iter().find(|other| other.overlaps(&range)).copied();
+        claimed.push(range);
+        match overlapping {
+            Some(other) => {
+                let error = ParseError::OverlappingTimeRanges(format!("{range} overlaps {other}"));
+                Err(match token {
+                    Some(token) => error.in_token(token),
+                    None => error,
+                })
+            }
+            None => Ok(()),
+        }
+    }
 }
 
 pub(crate) struct ParsedLine<'a> {
     pub(crate) content: &'a str,
     pub(crate) line_number: usize,
+    /// Width of the leading whitespace, a tab counting as four spaces.
+    pub(crate) indent: usize,
+    /// Byte offset of `content` in the line as written.
+    pub(crate) offset: usize,
+}
+
+impl<'a> ParsedLine<'a> {
+    pub(crate) fn new(line: &'a str, line_number: usize) -> Self {
+        let indent = line
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .map(|c| if c == '\t' { 4 } else { 1 })
+            .sum();
+        Self {
+            content: line.trim(),
+            line_number,
+            indent,
+            offset: line.len() - line.trim_start().len(),
+        }
+    }
 }
 
+#[allow(clippy::large_enum_variant)]
 pub(crate) enum LineType {
-    Header(Option<NaiveDate>),
-    Entry(TimeEntry),
+    Header {
+        level: usize,
+        date: Option<NaiveDate>,
+        record: DayRecord,
+        warnings: Vec<ParseError>,
+    },
+    Directive {
+        settings: String,
+        token: Range<usize>,
+    },
+    Entry(ParsedEntry),
     Other,
 }
 
 impl LineType {
-    pub(crate) fn parse(line: &str, in_tt_section: bool) -> Result<Self, ParseError> {
-        if line.starts_with('#') {
-            try_parse_to_header(line)
+    /// Reads a line; below a sub-heading with tags (`tags_in_scope`) entries need no tag of their own.
+    ///
+    /// Outside a TT section only lines that read as valid entries with time are entries, so
+    /// that the caller can warn about them; other lines there are not checked.
+    pub(crate) fn parse(
+        line: &str,
+        in_tt_section: bool,
+        tags_in_scope: bool,
+        config: &ParseConfig,
+    ) -> Result<Self, ParseError> {
+        if let Some(token) = directive_range(line) {
+            Ok(LineType::Directive {
+                settings: line[token.clone()].to_string(),
+                token,
+            })
+        } else if line.starts_with('#') {
+            try_parse_to_header(line, config)
         } else if in_tt_section {
-            try_parse_to_entry(line)
+            try_parse_to_entry(line, tags_in_scope, config)
         } else {
-            Ok(other())
+            match try_parse_to_entry(line, false, config) {
+                Ok(LineType::Entry(parsed)) if parsed.has_time => Ok(LineType::Entry(parsed)),
+                _ => Ok(other()),
+            }
         }
     }
 }
 
-fn try_parse_to_header(line: &str) -> Result<LineType, ParseError> {
-    let maybe_date = maybe_date_from_header(line);
-    let maybe_date = maybe_date.map(|date_str| {
-        NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-            .map_err(|_| ParseError::InvalidDate(date_str.to_string()))
-    });
-    let maybe_date = maybe_date.transpose()?;
-    Ok(LineType::Header(maybe_date))
+fn try_parse_to_header(line: &str, config: &ParseConfig) -> Result<LineType, ParseError> {
+    let header_date = maybe_date_from_header(line);
+    let maybe_date = header_date
+        .as_ref()
+        .map(|header_date| header_date.parse(&config.date_formats))
+        .transpose()?;
+    let (date, warning) = maybe_date.map_or((None, None), |(date, warning)| (Some(date), warning));
+    let (record, annotation_warnings) = header_date
+        .map(|header_date| header_date.day_record(&config.date_formats))
+        .unwrap_or_default();
+    Ok(LineType::Header {
+        level: header_level(line),
+        date,
+        record,
+        warnings: warning.into_iter().chain(annotation_warnings).collect(),
+    })
 }
 
-fn try_parse_to_entry(line: &str) -> Result<LineType, ParseError> {
-    match TimeEntry::parse(line) {
-        EntryLineParseResult::Entry(entry) => Ok(LineType::Entry(entry)),
-        EntryLineParseResult::NotAnEntry => Ok(other()),
-        EntryLineParseResult::Malformed(err) => Err(err),
+fn try_parse_to_entry(
+    line: &str,
+    tags_in_scope: bool,
+    config: &ParseConfig,
+) -> Result<LineType, ParseError> {
+    let parsed = if tags_in_scope {
+        TimeEntry::parse_scoped(line, config)
+    } else {
+        TimeEntry::parse_parent(line, config)
+    };
+    match parsed {
+        Some(parsed) => parsed.map(LineType::Entry),
+        None => Ok(other()),
     }
 }
 
@@ -66,6 +270,7 @@ pub struct ContentParseResults {
     errors: Vec<ParseError>,
     days: u32,
     entries: Option<HashMap<NaiveDate, Vec<TimeEntry>>>,
+    day_records: HashMap<NaiveDate, DayRecord>,
 }
 
 impl ContentParseResults {
@@ -75,6 +280,7 @@ impl ContentParseResults {
             errors,
             days: u32::try_from(entries.len()).unwrap_or(0),
             entries: Some(entries),
+            day_records: HashMap::new(),
         }
     }
 
@@ -84,9 +290,17 @@ impl ContentParseResults {
             errors,
             days: 0,
             entries: None,
+            day_records: HashMap::new(),
         }
     }
 
+    /// Adds the annotations of the TT headers, e.g. `[holiday]`.
+    #[must_use]
+    pub fn with_day_records(mut self, day_records: HashMap<NaiveDate, DayRecord>) -> Self {
+        self.day_records = day_records;
+        self
+    }
+
     #[must_use]
     pub fn errors(&self) -> Vec<ParseError> {
         self.errors.clone()
@@ -102,6 +316,11 @@ impl ContentParseResults {
         self.entries.as_ref()
     }
 
+    #[must_use]
+    pub fn day_records(&self) -> &HashMap<NaiveDate, DayRecord> {
+        &self.day_records
+    }
+
     #[must_use]
     pub fn merge(&self, other: &ContentParseResults) -> ContentParseResults {
         // Merge errors
@@ -125,9 +344,15 @@ impl ContentParseResults {
             (None, None) => None,
         };
 
+        let mut merged_records = self.day_records.clone();
+        for (date, record) in &other.day_records {
+            merged_records.entry(*date).or_default().merge(record);
+        }
+
         match merged_entries {
             Some(entries) => ContentParseResults::new(entries, merged_errors),
             None => ContentParseResults::errors_only(merged_errors),
         }
+        .with_day_records(merged_records)
     }
 }
@@ -1,32 +1,54 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
 use crate::parsing::filter::Filter;
 use crate::parsing::model::ContentParseResults;
 
+use crate::domain::config::ParseConfig;
 use crate::domain::dates::EntryDate;
-use crate::domain::{Location, ParseError};
+use crate::domain::diagnostics::Span;
+use crate::domain::time::TimeRange;
+use crate::domain::{Location, ParseError, TimeEntry};
 
-use super::{LineType, ParseState, ParsedLine};
+use super::directive_parser::apply_directive;
+use super::header_parser::HeaderScope;
+use super::{LineType, ParseState, ParsedLine, PendingEntry};
 
+/// Parses one file; `time_ranges` holds the clock ranges claimed by the files read before,
+/// so ranges overlapping one of another file are found, and takes this file's ranges.
 #[must_use]
 pub fn parse_content(
     content: &str,
     filter: Option<&Filter>,
     file_name: &str,
+    config: &ParseConfig,
+    note_date: Option<NaiveDate>,
+    time_ranges: &mut HashMap<NaiveDate, Vec<TimeRange>>,
 ) -> ContentParseResults {
-    let mut state = ParseState::default();
+    let mut state = note_date.map_or_else(
+        || ParseState::with_config(config),
+        |date| ParseState::for_daily_note(config, date),
+    );
+    state.time_ranges = std::mem::take(time_ranges);
 
     for (line_number, line) in content.lines().enumerate() {
-        let parsed_line = ParsedLine {
-            content: line.trim(),
-            line_number: line_number + 1,
-        };
+        let parsed_line = ParsedLine::new(line, line_number + 1);
         process_line_mut(&parsed_line, &mut state, filter, file_name);
     }
+    complete_pending_entry(&mut state, filter, file_name);
+    *time_ranges = std::mem::take(&mut state.time_ranges);
 
+    let day_records = state
+        .day_records
+        .into_iter()
+        .filter(|(date, _)| filter.is_none_or(|f| f.matches_date(&EntryDate(*date))))
+        .collect();
     if state.entries.is_empty() {
         ContentParseResults::errors_only(state.errors)
     } else {
         ContentParseResults::new(state.entries, state.errors)
     }
+    .with_day_records(day_records)
 }
 
 fn process_line_mut(
@@ -35,28 +57,172 @@ fn process_line_mut(
     filter: Option<&Filter>,
     file_name: &str,
 ) {
-    match LineType::parse(line.content, state.in_time_tracking_section()) {
-        Ok(LineType::Header(maybe_date)) => {
-            state.current_date = maybe_date;
+    if state.block.skips(line.content) {
+        // A code block, comment or quote ends the list, so no subtasks follow it.
+        complete_pending_entry(state, filter, file_name);
+        return;
+    }
+
+    if let Some(pending) = &state.pending {
+        if pending.is_parent_of(line) {
+            process_subtask_line_mut(line, state, file_name);
+            return;
+        }
+        if !line.content.is_empty() {
+            complete_pending_entry(state, filter, file_name);
+        }
+    }
+
+    let scope_tags = state.scope_tags();
+    match LineType::parse(
+        line.content,
+        state.in_time_tracking_section(),
+        !scope_tags.is_empty(),
+        &state.config,
+    ) {
+        Ok(LineType::Header {
+            level,
+            date,
+            record,
+            warnings,
+        }) => {
+            for warning in warnings {
+                push_located_error(state, warning, line, file_name);
+            }
+            match date {
+                Some(date) => state.start_day(date, level, &record),
+                None if state.is_sub_heading(level) => {
+                    let scope = HeaderScope::parse(line.content, &state.config.tags);
+                    state.enter_scope(scope);
+                }
+                None => state.end_day(),
+            }
         }
-        Ok(LineType::Entry(entry)) if state.in_time_tracking_section() => {
+        Ok(LineType::Directive { settings, token }) => {
+            if let Err(error) = apply_directive(&settings, &mut state.config) {
+                push_located_error(state, error.in_token(token), line, file_name);
+            }
+        }
+        Ok(LineType::Entry(parsed)) if state.in_time_tracking_section() => {
+            for warning in parsed.warnings {
+                push_located_error(state, warning, line, file_name);
+            }
             if let Some(date) = state.current_date {
-                let entry_matches_filter =
-                    filter.is_none_or(|f| f.matches(&entry, &EntryDate(date)));
-                if entry_matches_filter {
-                    state.entries.entry(date).or_default().push(entry);
+                let mut entry = parsed
+                    .entry
+                    .with_scope(&scope_tags, state.scope_outcome())
+                    .written_at(date, location(line, file_name));
+                if let Some(day) = state.current_day() {
+                    entry = entry.with_day(day);
+                }
+                state.pending = Some(PendingEntry {
+                    date,
+                    entry,
+                    has_time: parsed.has_time,
+                    subtasks: Vec::new(),
+                    indent: line.indent,
+                    line: line.content.to_string(),
+                    line_number: line.line_number,
+                    offset: line.offset,
+                    range_token: parsed.range_token,
+                });
+            }
+        }
+        Ok(LineType::Entry(_)) => {
+            let error = ParseError::EntryOutsideSection(line.content.to_string());
+            push_located_error(state, error, line, file_name);
+        }
+        Err(error) => push_located_error(state, error, line, file_name),
+        Ok(LineType::Other) => {}
+    }
+}
+
+/// Collects an indented `- ` line as a subtask of the pending entry; other indented lines are notes.
+fn process_subtask_line_mut(line: &ParsedLine, state: &mut ParseState, file_name: &str) {
+    match TimeEntry::parse_subtask(line.content, &state.config) {
+        Some(Ok(parsed)) => {
+            for warning in parsed.warnings {
+                push_located_error(state, warning, line, file_name);
+            }
+            if let Some(pending) = state.pending.as_mut() {
+                let subtask = parsed
+                    .entry
+                    .written_at(pending.date, location(line, file_name));
+                // A parent's own range already covers the ranges of its subtasks.
+                let range = subtask
+                    .time_range
+                    .filter(|_| pending.entry.time_range.is_none());
+                let date = pending.date;
+                pending.subtasks.push(subtask);
+                if let Some(range) = range {
+                    if let Err(error) = state.claim_time_range(date, range, parsed.range_token) {
+                        push_located_error(state, error, line, file_name);
+                    }
                 }
             }
         }
+        Some(Err(error)) => push_located_error(state, error, line, file_name),
+        None => {}
+    }
+}
+
+fn complete_pending_entry(state: &mut ParseState, filter: Option<&Filter>, file_name: &str) {
+    let Some(pending) = state.pending.take() else {
+        return;
+    };
+    let line = ParsedLine {
+        content: &pending.line,
+        line_number: pending.line_number,
+        indent: pending.indent,
+        offset: pending.offset,
+    };
+
+    let entry = match pending
+        .entry
+        .with_subtasks(pending.subtasks, pending.has_time, &pending.line)
+    {
+        Ok((entry, warning)) => {
+            if let Some(warning) = warning {
+                push_located_error(state, warning, &line, file_name);
+            }
+            entry
+        }
         Err(error) => {
-            state.errors.push(ParseError::Located {
-                error: Box::new(error),
-                location: Location {
-                    file: file_name.to_string(),
-                    line: line.line_number,
-                },
-            });
+            push_located_error(state, error, &line, file_name);
+            return;
         }
-        _ => {}
+    };
+
+    if let Some(range) = entry.time_range {
+        if let Err(error) = state.claim_time_range(pending.date, range, pending.range_token) {
+            push_located_error(state, error, &line, file_name);
+        }
+    }
+
+    let entry_matches_filter = filter.is_none_or(|f| f.matches(&entry, &EntryDate(pending.date)));
+    if entry_matches_filter {
+        state.entries.entry(pending.date).or_default().push(entry);
     }
 }
+
+fn location(line: &ParsedLine, file_name: &str) -> Location {
+    Location {
+        file: file_name.to_string(),
+        line: line.line_number,
+    }
+}
+
+fn push_located_error(
+    state: &mut ParseState,
+    error: ParseError,
+    line: &ParsedLine,
+    file_name: &str,
+) {
+    let (error, token) = error.split_token();
+    let span = Span::of(token, line.content, line.offset);
+    state.errors.push(ParseError::Located {
+        error: Box::new(error),
+        location: location(line, file_name),
+        span: Some(span),
+    });
+}
@@ -10,6 +10,7 @@ pub(super) trait FileProcessor {
 
 mod processors {
     use super::{FileProcessor, ParseError, Path, Processor as InputProcessor, WalkDir};
+    use chrono::NaiveDate;
     use std::fs::read_to_string;
 
     #[derive(Debug)]
@@ -30,6 +31,36 @@ mod processors {
         pub fn file_name(&self) -> &str {
             &self.file_name
         }
+
+        /// The day this file is a daily note of: the `date:` in its front matter,
+        /// or else its file name, e.g. `2025-01-15.md` or `2025_01_15.md`.
+        pub fn daily_note_date(&self, formats: &[String]) -> Option<NaiveDate> {
+            let parse = |value: &str| {
+                formats
+                    .iter()
+                    .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+            };
+            let file_stem = Path::new(&self.file_name)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .map(|stem| stem.replace('_', "-"));
+
+            front_matter_date(&self.content)
+                .and_then(parse)
+                .or_else(|| file_stem.as_deref().and_then(parse))
+        }
+    }
+
+    /// The `date:` value of a YAML front matter block at the start of the content.
+    fn front_matter_date(content: &str) -> Option<&str> {
+        let mut lines = content.lines();
+        if lines.next()?.trim() != "---" {
+            return None;
+        }
+        lines
+            .take_while(|line| line.trim() != "---")
+            .find_map(|line| line.strip_prefix("date:"))
+            .map(|value| value.trim().trim_matches(|c| c == '"' || c == '\''))
     }
 
     #[derive(Debug)]
@@ -42,6 +73,20 @@ mod processors {
             })
         }
 
+        /// Reads the file as `file_name`, the name warnings and entry sources refer to it by.
+        fn process_named<F>(
+            &self,
+            path: &Path,
+            file_name: String,
+            mut processor: F,
+        ) -> Result<(), ParseError>
+        where
+            F: FnMut(ProcessingInput) -> Result<(), ParseError>,
+        {
+            let content = Self::read_file_content(path)?;
+            processor(ProcessingInput::new(content, file_name))
+        }
+
         fn extract_file_name(path: &Path) -> Result<String, ParseError> {
             path.file_name()
                 .and_then(|n| n.to_str())
@@ -53,14 +98,12 @@ mod processors {
     }
 
     impl FileProcessor for SingleFileProcessor {
-        fn process<F>(&self, path: &Path, mut processor: F) -> Result<(), ParseError>
+        fn process<F>(&self, path: &Path, processor: F) -> Result<(), ParseError>
         where
             F: FnMut(ProcessingInput) -> Result<(), ParseError>,
         {
             let file_name = Self::extract_file_name(path)?;
-            let content = Self::read_file_content(path)?;
-
-            processor(ProcessingInput::new(content, file_name))
+            self.process_named(path, file_name, processor)
         }
     }
 
@@ -85,11 +128,17 @@ mod processors {
             for entry in WalkDir::new(path)
                 .follow_links(false)
                 .max_depth(10)
+                .sort_by_file_name()
                 .into_iter()
                 .filter_map(Result::ok)
                 .filter(|e| is_supported_file(e.path()))
             {
-                self.file_processor.process(entry.path(), &mut processor)?;
+                let relative_path = entry.path().strip_prefix(path).unwrap_or(entry.path());
+                self.file_processor.process_named(
+                    entry.path(),
+                    relative_path.to_string_lossy().into_owned(),
+                    &mut processor,
+                )?;
             }
             Ok(())
         }
@@ -208,6 +257,41 @@ mod tests {
         Ok(())
     }
 
+    mod daily_notes {
+        use super::*;
+        use crate::domain::config::ParseConfig;
+        use chrono::NaiveDate;
+
+        fn daily_note_date(file_name: &str, content: &str) -> Option<NaiveDate> {
+            ProcessingInput::new(content.to_string(), file_name.to_string())
+                .daily_note_date(&ParseConfig::default().date_formats)
+        }
+
+        fn date(day: u32) -> Option<NaiveDate> {
+            NaiveDate::from_ymd_opt(2025, 1, day)
+        }
+
+        #[test]
+        fn date_should_come_from_file_name() {
+            assert_eq!(daily_note_date("2025-01-15.md", ""), date(15));
+            assert_eq!(daily_note_date("2025_01_15.md", ""), date(15));
+        }
+
+        #[test]
+        fn date_should_come_from_front_matter_before_file_name() {
+            let content = "---\ntitle: Standup\ndate: \"2025-01-16\"\n---\n- #dev 1h";
+
+            assert_eq!(daily_note_date("2025-01-15.md", content), date(16));
+        }
+
+        #[test]
+        fn date_should_ignore_date_outside_front_matter() {
+            let content = "# Notes\ndate: 2025-01-16";
+
+            assert_eq!(daily_note_date("notes.md", content), None);
+        }
+    }
+
     struct ProcessingTest {
         temp_dir: assert_fs::TempDir,
     }
@@ -0,0 +1,151 @@
+use serde_json::{json, Value};
+
+use crate::domain::config::UnitDefinition;
+use crate::domain::reporting::{
+    BreakdownGroup, BreakdownReport, DetailReport, EstimateReport, LogReport, OverviewReport,
+    TaskSummary,
+};
+use crate::domain::{TimeEntry, TrackingPeriod};
+use crate::reporting::format::Formatter;
+use crate::reporting::model::FormatableReport;
+
+/// Reports as JSON documents for other tools, with durations in minutes.
+pub struct JsonFormatter;
+
+impl Formatter for JsonFormatter {
+    fn format(&self, report: &FormatableReport) -> String {
+        let document = match report {
+            FormatableReport::OverviewReport(report) => overview(report),
+            FormatableReport::TasksReport(report) => details(report),
+            FormatableReport::BreakdownReport(report) => breakdown(report),
+            FormatableReport::EstimateReport(report) => estimates(report),
+            FormatableReport::LogReport(report) => log(report),
+        };
+        serde_json::to_string_pretty(&document).expect("Serializing JSON should never fail")
+    }
+
+    /// Durations are already in minutes, so the output stays a single JSON document.
+    fn format_units(&self, _units: &[UnitDefinition]) -> String {
+        String::new()
+    }
+}
+
+fn period(period: &TrackingPeriod) -> Value {
+    json!({
+        "start": period.start.0.to_string(),
+        "end": period.end.0.to_string(),
+        "days": period.days,
+    })
+}
+
+fn overview(report: &OverviewReport) -> Value {
+    json!({
+        "report": "overview",
+        "period": period(report.period()),
+        "total_minutes": report.total_minutes(),
+        "projects": report.entries_time_totals(),
+        "outcomes": report.outcome_time_totals(),
+        "statuses": report.status_time_totals(),
+    })
+}
+
+fn details(report: &DetailReport) -> Value {
+    let projects: Vec<Value> = report
+        .summaries()
+        .iter()
+        .map(|summary| {
+            json!({
+                "project": summary.context().raw_value(),
+                "total_minutes": summary.total_minutes(),
+                "tasks": tasks(summary.task_summaries()),
+            })
+        })
+        .collect();
+    json!({
+        "report": "details",
+        "period": period(report.period()),
+        "total_minutes": report.total_minutes(),
+        "projects": projects,
+    })
+}
+
+fn tasks(tasks: &[TaskSummary]) -> Vec<Value> {
+    tasks
+        .iter()
+        .map(|task| {
+            json!({
+                "description": task.description,
+                "minutes": task.minutes,
+                "percentage": task.percentage_of_total,
+                "level": task.level,
+            })
+        })
+        .collect()
+}
+
+fn breakdown(report: &BreakdownReport) -> Value {
+    json!({
+        "report": "breakdown",
+        "period": period(&report.period),
+        "total_minutes": report.total_minutes,
+        "groups": report.groups.iter().map(breakdown_group).collect::<Vec<_>>(),
+    })
+}
+
+fn breakdown_group(group: &BreakdownGroup) -> Value {
+    json!({
+        "label": group.label,
+        "minutes": group.minutes,
+        "children": group.children.iter().map(breakdown_group).collect::<Vec<_>>(),
+        "tasks": tasks(&group.tasks),
+    })
+}
+
+fn estimates(report: &EstimateReport) -> Value {
+    let projects: Vec<Value> = report
+        .summaries()
+        .iter()
+        .map(|summary| {
+            let tasks: Vec<Value> = summary
+                .tasks()
+                .iter()
+                .map(|task| {
+                    json!({
+                        "description": task.description,
+                        "estimate_minutes": task.estimate_minutes,
+                        "actual_minutes": task.actual_minutes,
+                        "variance_minutes": task.variance_minutes(),
+                    })
+                })
+                .collect();
+            json!({ "project": summary.context().raw_value(), "tasks": tasks })
+        })
+        .collect();
+    json!({
+        "report": "estimates",
+        "period": period(report.period()),
+        "projects": projects,
+    })
+}
+
+fn log(report: &LogReport) -> Value {
+    json!({
+        "report": "log",
+        "period": period(report.period()),
+        "total_minutes": report.total_minutes(),
+        "entries": report.entries().iter().map(entry).collect::<Vec<_>>(),
+    })
+}
+
+fn entry(entry: &TimeEntry) -> Value {
+    json!({
+        "date": entry.date.map(|date| date.to_string()),
+        "file": entry.source.as_ref().map(|source| &source.file),
+        "line": entry.source.as_ref().map(|source| source.line),
+        "minutes": entry.minutes,
+        "tags": entry.get_tags().iter().map(|tag| tag.raw_value()).collect::<Vec<_>>(),
+        "outcome": entry.outcome.as_ref().map(|outcome| outcome.description()),
+        "description": entry.description,
+        "status": entry.status.map(|status| status.description()),
+    })
+}
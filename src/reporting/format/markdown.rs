@@ -1,7 +1,11 @@
 use std::fmt::Write;
 
-use crate::domain::reporting::{DetailReport, TimeTotal};
-use crate::reporting::format::{format_duration, Formatter};
+use crate::domain::config::UnitDefinition;
+use crate::domain::reporting::{DetailReport, EstimateReport, LogReport, TimeTotal};
+use crate::reporting::format::{
+    format_duration, format_entry_outcome, format_entry_tags, format_unit_definitions,
+    format_variance, format_working_days, Formatter,
+};
 use crate::reporting::model::FormatableReport;
 
 pub struct MarkdownFormatter;
@@ -11,18 +15,25 @@ impl Formatter for MarkdownFormatter {
         match report {
             FormatableReport::OverviewReport(report) => Self::format_overview(
                 report.entries_time_totals().clone(),
+                report.status_time_totals(),
                 report.period(),
                 report.total_minutes(),
             ),
             FormatableReport::TasksReport(report) => Self::format_tasks_report(report),
             FormatableReport::BreakdownReport(report) => Self::format_breakdown_report(report),
+            FormatableReport::EstimateReport(report) => Self::format_estimate_report(report),
+            FormatableReport::LogReport(report) => Self::format_log_report(report),
         }
     }
+    fn format_units(&self, units: &[UnitDefinition]) -> String {
+        format!("_Units: {}_", format_unit_definitions(units))
+    }
 }
 
 impl MarkdownFormatter {
     fn format_overview(
         entries: Vec<TimeTotal>,
+        statuses: &[TimeTotal],
         period: &crate::domain::TrackingPeriod,
         total_minutes: u32,
     ) -> String {
@@ -32,26 +43,46 @@ impl MarkdownFormatter {
         let hours_per_day = (f64::from(total_minutes) / 60.0) / f64::from(period.days);
         write!(
             &mut result,
-            "# Time Tracking Report\n\n## Overview\n\n- **Period**: {} -> {}\n- **Days Tracked**: {}\n- **Hours per Day**: {:.1}\n- **Total Time**: {}\n\n",
+            "# Time Tracking Report\n\n## Overview\n\n- **Period**: {} -> {}\n- **Days Tracked**: {}\n{}- **Hours per Day**: {:.1}\n- **Total Time**: {}\n\n",
             period.start.0.format("%Y-%m-%d"),
             period.end.0.format("%Y-%m-%d"),
             period.days,
+            format_working_days_item(period),
             hours_per_day,
             format_duration(total_minutes)
         ).expect("Writing to String should never fail");
 
         result.push_str("### Projects\n");
         for entry in entries {
+            let label = match &entry.link {
+                Some(link) => format!("[{}]({link})", entry.description),
+                None => entry.description.clone(),
+            };
             writeln!(
                 &mut result,
-                "- **{}**: {} ({}%)",
-                entry.description,
+                "{}- **{}**: {} ({}%)",
+                "  ".repeat(entry.level),
+                label,
                 format_duration(entry.minutes),
                 entry.percentage
             )
             .expect("Writing to String should never fail");
         }
 
+        if !statuses.is_empty() {
+            result.push_str("\n### Tasks\n");
+            for status in statuses {
+                writeln!(
+                    &mut result,
+                    "- **{}**: {} ({}%)",
+                    status.description,
+                    format_duration(status.minutes),
+                    status.percentage
+                )
+                .expect("Writing to String should never fail");
+            }
+        }
+
         result
     }
 
@@ -89,7 +120,8 @@ impl MarkdownFormatter {
             for task in &group.tasks {
                 writeln!(
                     result,
-                    "- **{}**: {} ({}%)",
+                    "{}- **{}**: {} ({}%)",
+                    "  ".repeat(task.level),
                     task.description,
                     format_duration(task.minutes),
                     task.percentage_of_total
@@ -112,6 +144,84 @@ impl MarkdownFormatter {
         }
     }
 
+    fn format_estimate_report(report: &EstimateReport) -> String {
+        let mut result = String::new();
+        writeln!(
+            &mut result,
+            "# Estimates Report\n\n- **Period**: {} -> {}\n",
+            report.period().start.0.format("%Y-%m-%d"),
+            report.period().end.0.format("%Y-%m-%d"),
+        )
+        .expect("Writing to String should never fail");
+
+        if report.summaries().is_empty() {
+            result.push_str("No estimates found.\n");
+        }
+        for context_summary in report.summaries() {
+            writeln!(
+                &mut result,
+                "## Project: {}\n",
+                context_summary.context().raw_value()
+            )
+            .expect("Writing to String should never fail");
+            result.push_str("| Task | Estimate | Actual | Variance |\n");
+            result.push_str("|------|----------|--------|----------|\n");
+            for task in context_summary.tasks() {
+                writeln!(
+                    &mut result,
+                    "| {} | {} | {} | {} |",
+                    task.description,
+                    format_duration(task.estimate_minutes).trim_start(),
+                    format_duration(task.actual_minutes).trim_start(),
+                    format_variance(task)
+                )
+                .expect("Writing to String should never fail");
+            }
+            result.push('\n');
+        }
+
+        result
+    }
+
+    fn format_log_report(report: &LogReport) -> String {
+        let mut result = String::new();
+        writeln!(
+            &mut result,
+            "# Log\n\n- **Period**: {} -> {}\n- **Total Time**: {}\n",
+            report.period().start.0.format("%Y-%m-%d"),
+            report.period().end.0.format("%Y-%m-%d"),
+            format_duration(report.total_minutes())
+        )
+        .expect("Writing to String should never fail");
+
+        result.push_str("| Date | Duration | Tags | Outcome | Description | Source |\n");
+        result.push_str("|------|----------|------|---------|-------------|--------|\n");
+        for entry in report.entries() {
+            writeln!(
+                &mut result,
+                "| {} | {} | {} | {} | {} | {} |",
+                entry
+                    .date
+                    .map_or_else(String::new, |date| date.format("%Y-%m-%d").to_string()),
+                format_duration(entry.minutes).trim_start(),
+                format_entry_tags(entry),
+                format_entry_outcome(entry),
+                entry
+                    .description
+                    .as_deref()
+                    .unwrap_or("")
+                    .replace('|', "\\|"),
+                entry
+                    .source
+                    .as_ref()
+                    .map_or_else(String::new, |source| format!("`{source}`"))
+            )
+            .expect("Writing to String should never fail");
+        }
+
+        result
+    }
+
     fn format_tasks_report(report: &DetailReport) -> String {
         let mut result = String::new();
 
@@ -143,10 +253,11 @@ impl MarkdownFormatter {
         let hours_per_day = (f64::from(total_minutes) / 60.0) / f64::from(period.days);
         write!(
             &mut result,
-            "- **Period**: {} -> {}\n- **Days Tracked**: {}\n- **Hours per Day**: {:.1}\n- **Total Time**: {}\n\n",
+            "- **Period**: {} -> {}\n- **Days Tracked**: {}\n{}- **Hours per Day**: {:.1}\n- **Total Time**: {}\n\n",
             period.start.0.format("%Y-%m-%d"),
             period.end.0.format("%Y-%m-%d"),
             period.days,
+            format_working_days_item(period),
             hours_per_day,
             format_duration(total_minutes)
         ).expect("Writing to String should never fail");
@@ -155,7 +266,8 @@ impl MarkdownFormatter {
         for task in tasks {
             writeln!(
                 &mut result,
-                "- **{}**: {} ({}%)",
+                "{}- **{}**: {} ({}%)",
+                "  ".repeat(task.level),
                 task.description,
                 format_duration(task.minutes),
                 task.percentage_of_total
@@ -167,3 +279,15 @@ impl MarkdownFormatter {
         result
     }
 }
+
+/// The working days list item, when leave was taken in the period.
+fn format_working_days_item(period: &crate::domain::TrackingPeriod) -> String {
+    if period.working_days.has_leave() {
+        format!(
+            "- **Working Days**: {}\n",
+            format_working_days(&period.working_days)
+        )
+    } else {
+        String::new()
+    }
+}
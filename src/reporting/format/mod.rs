@@ -1,28 +1,40 @@
 //! Module for report formatting in different output formats
 
+mod json;
 mod markdown;
 mod text;
 
+use crate::reporting::format::json::JsonFormatter;
 use crate::reporting::format::markdown::MarkdownFormatter;
 use crate::reporting::format::text::TextFormatter;
 
 use super::model::FormatableReport;
+use crate::domain::config::UnitDefinition;
+use crate::domain::days::WorkingDays;
+use crate::domain::reporting::TaskEstimate;
+use crate::domain::TimeEntry;
 
 fn create_formatter(format_type: FormatType) -> Box<dyn Formatter> {
     match format_type {
         FormatType::Text => Box::new(TextFormatter),
         FormatType::Markdown => Box::new(MarkdownFormatter),
+        FormatType::Json => Box::new(JsonFormatter),
     }
 }
 
 pub trait Formatter {
     fn format(&self, report: &FormatableReport) -> String;
+
+    /// A note on the unit lengths the durations were read with, e.g. a 25 minute pomodoro;
+    /// empty when the format needs none.
+    fn format_units(&self, units: &[UnitDefinition]) -> String;
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FormatType {
     Text,
     Markdown,
+    Json,
 }
 
 impl dyn Formatter {
@@ -43,6 +55,7 @@ impl FormatType {
     pub fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "markdown" | "md" => FormatType::Markdown,
+            "json" => FormatType::Json,
             _ => FormatType::Text,
         }
     }
@@ -53,3 +66,81 @@ pub(crate) fn format_duration(minutes: u32) -> String {
     let remaining_minutes = minutes % 60;
     format!("{hours:2}h {remaining_minutes:02}m")
 }
+
+/// Time over or under the estimate, e.g. `+0h 30m (17% over)`.
+pub(crate) fn format_variance(task: &TaskEstimate) -> String {
+    let variance = task.variance_minutes();
+    let sign = if variance < 0 { '-' } else { '+' };
+    let minutes = u32::try_from(variance.unsigned_abs()).unwrap_or(u32::MAX);
+    let percentage = match task.variance_percentage() {
+        Some(0) => "on estimate".to_string(),
+        Some(percentage) if percentage > 0 => format!("{percentage}% over"),
+        Some(percentage) => format!("{}% under", percentage.abs()),
+        None => "no estimate".to_string(),
+    };
+    format!(
+        "{sign}{} ({percentage})",
+        format_duration(minutes).trim_start()
+    )
+}
+
+/// Days worked with the leave taken, e.g. `3.5 (1 holiday, 1 half day)`.
+pub(crate) fn format_working_days(working_days: &WorkingDays) -> String {
+    let leave: Vec<String> = [
+        (working_days.holidays, "holiday", "holidays"),
+        (working_days.sick_days, "sick day", "sick days"),
+        (working_days.half_days, "half day", "half days"),
+    ]
+    .into_iter()
+    .filter(|(count, _, _)| *count > 0)
+    .map(|(count, one, many)| format!("{count} {}", if count == 1 { one } else { many }))
+    .collect();
+    let worked = working_days.worked();
+    let worked = if worked.fract() == 0.0 {
+        format!("{worked:.0}")
+    } else {
+        format!("{worked:.1}")
+    };
+    if leave.is_empty() {
+        worked
+    } else {
+        format!("{worked} ({})", leave.join(", "))
+    }
+}
+
+/// Unit lengths as they are written in durations, e.g. `p = 25m, block = 1h30m`.
+pub(crate) fn format_unit_definitions(units: &[UnitDefinition]) -> String {
+    units
+        .iter()
+        .map(|definition| {
+            let (hours, minutes) = (definition.minutes / 60, definition.minutes % 60);
+            let length = match (hours, minutes) {
+                (0, minutes) => format!("{minutes}m"),
+                (hours, 0) => format!("{hours}h"),
+                (hours, minutes) => format!("{hours}h{minutes}m"),
+            };
+            format!("{} = {length}", definition.unit)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Tags of an entry as they are written, e.g. `#prj-1 #meeting`.
+pub(crate) fn format_entry_tags(entry: &TimeEntry) -> String {
+    entry
+        .get_tags()
+        .iter()
+        .map(|tag| format!("#{}", tag.raw_value()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Outcomes of an entry as written, e.g. `##launch:70% ##hiring:30%`, or empty without one.
+pub(crate) fn format_entry_outcome(entry: &TimeEntry) -> String {
+    entry
+        .outcomes_as_written()
+        .iter()
+        .map(|outcome| format!("##{outcome}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
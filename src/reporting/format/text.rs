@@ -4,11 +4,17 @@ use crate::domain::reporting;
 use crate::domain::reporting::DetailReport;
 use crate::domain::reporting::TimeTotal;
 
+use crate::domain::config::UnitDefinition;
 use crate::domain::reporting::OverviewReport;
 use crate::domain::TrackingPeriod;
 use crate::domain::{PeriodDescription, PeriodRequested};
 
 use crate::reporting::format::format_duration;
+use crate::reporting::format::format_entry_outcome;
+use crate::reporting::format::format_entry_tags;
+use crate::reporting::format::format_unit_definitions;
+use crate::reporting::format::format_variance;
+use crate::reporting::format::format_working_days;
 use crate::reporting::format::Formatter;
 use crate::reporting::model::FormatableReport;
 
@@ -20,8 +26,13 @@ impl Formatter for TextFormatter {
             FormatableReport::TasksReport(report) => Self::format_tasks_report(report),
             FormatableReport::OverviewReport(report) => Self::format_overview_report(report),
             FormatableReport::BreakdownReport(report) => Self::format_breakdown_report(report),
+            FormatableReport::EstimateReport(report) => Self::format_estimate_report(report),
+            FormatableReport::LogReport(report) => Self::format_log_report(report),
         }
     }
+    fn format_units(&self, units: &[UnitDefinition]) -> String {
+        format!("Units: {}", format_unit_definitions(units))
+    }
 }
 
 fn format_interval(period: &TrackingPeriod) -> String {
@@ -41,6 +52,7 @@ impl TextFormatter {
         Self::format_overview(
             report.entries_time_totals(),
             report.outcome_time_totals(),
+            report.status_time_totals(),
             report.period(),
             description.as_ref(),
             report.total_minutes(),
@@ -50,6 +62,7 @@ impl TextFormatter {
     fn format_overview(
         entries: &[TimeTotal],
         outcomes: &[TimeTotal],
+        statuses: &[TimeTotal],
         period: &TrackingPeriod,
         range_description: Option<&PeriodDescription>,
         total_minutes: u32,
@@ -67,7 +80,7 @@ impl TextFormatter {
             writeln!(
                 &mut result,
                 "{:.<20}..{} ({:>3}%)",
-                entry.description,
+                format_tree_label(&entry.description, entry.level),
                 format_duration(entry.minutes),
                 entry.percentage
             )
@@ -89,6 +102,21 @@ impl TextFormatter {
             }
         }
 
+        if !statuses.is_empty() {
+            result.push('\n');
+            writeln!(&mut result, "Tasks:").expect("Writing to String should never fail");
+            for status in statuses {
+                writeln!(
+                    &mut result,
+                    "* {:.<20}..{} ({:>3}%)",
+                    status.description,
+                    format_duration(status.minutes),
+                    status.percentage
+                )
+                .expect("Writing to String should never fail");
+            }
+        }
+
         result
     }
 
@@ -131,12 +159,12 @@ impl TextFormatter {
         }
 
         for task in &group.tasks {
-            let task_indent = "  ".repeat(depth + 1);
+            let task_indent = "  ".repeat(depth + 1 + task.level);
             writeln!(
                 result,
                 "{}- {}..{} ({}%)",
                 task_indent,
-                format_padded_description(&task.description),
+                format_padded_description(&task.description, task.level),
                 format_duration(task.minutes),
                 task.percentage_of_total
             )
@@ -144,6 +172,81 @@ impl TextFormatter {
         }
     }
 
+    fn format_estimate_report(report: &reporting::EstimateReport) -> String {
+        let mut result = String::new();
+        result.push_str("Estimates report\n");
+        result.push_str(&format_interval(report.period()));
+        result.push('\n');
+
+        if report.summaries().is_empty() {
+            result.push_str("\nNo estimates found.\n");
+        }
+        for context_summary in report.summaries() {
+            result.push('\n');
+            writeln!(
+                &mut result,
+                "Project: {}",
+                context_summary.context().raw_value()
+            )
+            .expect("Writing to String should never fail");
+            for task in context_summary.tasks() {
+                writeln!(
+                    &mut result,
+                    "- {}{} of{} estimated, {}",
+                    format_padded_description(&task.description, 0),
+                    format_duration(task.actual_minutes),
+                    format_duration(task.estimate_minutes),
+                    format_variance(task)
+                )
+                .expect("Writing to String should never fail");
+            }
+        }
+
+        result
+    }
+
+    fn format_log_report(report: &reporting::LogReport) -> String {
+        let mut result = String::new();
+        result.push_str("Log\n");
+        result.push_str(&format_interval(report.period()));
+        result.push('\n');
+        writeln!(
+            &mut result,
+            "Total: {}",
+            format_duration(report.total_minutes())
+        )
+        .expect("Writing to String should never fail");
+        result.push('\n');
+
+        for entry in report.entries() {
+            let source = entry
+                .source
+                .as_ref()
+                .map_or_else(String::new, |source| format!("({source})"));
+            let details: Vec<String> = [
+                format_entry_tags(entry),
+                format_entry_outcome(entry),
+                entry.description.clone().unwrap_or_default(),
+                source,
+            ]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect();
+            writeln!(
+                &mut result,
+                "{} {} {}",
+                entry
+                    .date
+                    .map_or_else(String::new, |date| date.format("%Y-%m-%d").to_string()),
+                format_duration(entry.minutes).trim_start(),
+                details.join(" ")
+            )
+            .expect("Writing to String should never fail");
+        }
+
+        result
+    }
+
     fn format_tasks_report(report: &DetailReport) -> String {
         let mut result = String::new();
         for context_summary in report.summaries() {
@@ -177,8 +280,9 @@ impl TextFormatter {
         for task in tasks {
             writeln!(
                 &mut result,
-                "- {}{} ({}%)",
-                format_padded_description(&task.description),
+                "{}- {}{} ({}%)",
+                "  ".repeat(task.level),
+                format_padded_description(&task.description, task.level),
                 format_duration(task.minutes),
                 task.percentage_of_total
             )
@@ -191,19 +295,33 @@ impl TextFormatter {
 
 fn format_time_statistics(period: &TrackingPeriod, total_minutes: u32) -> String {
     let hours_per_day = (f64::from(total_minutes) / 60.0) / f64::from(period.days);
-    format!(
+    let mut statistics = format!(
         "{} days, {:.1} h/day, {} total\n",
         period.days,
         hours_per_day,
         format_duration(total_minutes)
-    )
+    );
+    if period.working_days.has_leave() {
+        writeln!(
+            &mut statistics,
+            "Working days: {}",
+            format_working_days(&period.working_days)
+        )
+        .expect("Writing to String should never fail");
+    }
+    statistics
+}
+
+fn format_tree_label(description: &str, level: usize) -> String {
+    format!("{}{description}", "  ".repeat(level))
 }
 
-fn format_padded_description(desc: &str) -> String {
+/// The description padded with dots, less for subtasks so durations stay in one column.
+fn format_padded_description(desc: &str, level: usize) -> String {
     format!(
         "{}..{}",
         desc,
-        ".".repeat(20_usize.saturating_sub(desc.len()))
+        ".".repeat(20_usize.saturating_sub(desc.len() + 2 * level))
     )
 }
 
@@ -38,7 +38,7 @@ mod tests {
 
         pub(crate) fn create_test_entry(tag: &str, minutes: u32) -> TimeEntry {
             match TimeEntry::parse(format!("- #{tag} {minutes}m").as_str()) {
-                EntryLineParseResult::Entry(entry) => entry,
+                EntryLineParseResult::Entry { entry, .. } => *entry,
                 EntryLineParseResult::NotAnEntry => panic!("Expected entry line to parse as entry"),
                 EntryLineParseResult::Malformed(err) => panic!("Parse error: {err:?}"),
             }
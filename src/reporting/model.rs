@@ -1,8 +1,12 @@
-use crate::domain::reporting::{BreakdownReport, DetailReport, OverviewReport};
+use crate::domain::reporting::{
+    BreakdownReport, DetailReport, EstimateReport, LogReport, OverviewReport,
+};
 
 #[allow(clippy::enum_variant_names)]
 pub enum FormatableReport<'a> {
     TasksReport(&'a DetailReport),
     OverviewReport(&'a OverviewReport),
     BreakdownReport(&'a BreakdownReport),
+    EstimateReport(&'a EstimateReport),
+    LogReport(&'a LogReport),
 }
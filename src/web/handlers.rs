@@ -9,11 +9,13 @@ use std::sync::Arc;
 use crate::domain::dates::range::DateRange;
 use crate::domain::dates::{EndDate, StartDate};
 use crate::domain::reporting::{OutputLimit, OverviewReport, TimeTotal};
+use crate::domain::tags::TAG_PATH_SEPARATOR;
 use crate::domain::time::Clock;
 use crate::domain::PeriodRequested;
 use crate::parsing;
 use crate::parsing::filter::Filter;
 use crate::cli::statistics::{read_flag_statistics, FlagStat};
+use crate::config::Config;
 
 use super::models::{DashboardParams, FlagStatsParams};
 
@@ -70,6 +72,7 @@ impl IntoResponse for WebError {
 pub struct AppState {
     pub data_path: Option<PathBuf>,
     pub clock: Clock,
+    pub config: Config,
 }
 
 fn format_minutes(minutes: u32) -> String {
@@ -89,9 +92,10 @@ fn format_minutes(minutes: u32) -> String {
 fn is_valid_tag(tag: &str) -> bool {
     !tag.is_empty()
         && tag.len() < 256
+        && tag.split(TAG_PATH_SEPARATOR).all(|level| !level.is_empty())
         && tag
             .chars()
-            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == TAG_PATH_SEPARATOR)
 }
 
 fn extract_filter_from_params(
@@ -135,8 +139,9 @@ async fn load_overview(
         .as_ref()
         .and_then(|p| PeriodRequested::from_str(p, &state.clock).ok());
 
+    let config = state.config.parsing.clone();
     let tracking_result = tokio::task::spawn_blocking(move || {
-        parsing::process_input(&data_path, filter.as_ref())
+        parsing::process_input_with_config(&data_path, filter.as_ref(), &config)
     })
     .await
     .map_err(|e| WebError::DataProcessingFailed(format!("Task failed: {}", e)))?
@@ -281,8 +286,32 @@ pub struct TagDetailTemplate {
 }
 
 pub struct EntryDisplay {
+    pub date: String,
     pub description: String,
     pub duration: u32,
+    pub tickets: Vec<TicketLink>,
+    /// The file and line the entry was written on, e.g. `2025-01.md:12`.
+    pub source: String,
+}
+
+/// A ticket key of an entry, with its link if a ticket URL is configured.
+pub struct TicketLink {
+    pub key: String,
+    pub url: Option<String>,
+}
+
+fn ticket_links(tickets: &[String], config: &Config) -> Vec<TicketLink> {
+    tickets
+        .iter()
+        .map(|key| TicketLink {
+            url: config
+                .reporting
+                .ticket_url
+                .as_ref()
+                .map(|url| url.link(key)),
+            key: key.clone(),
+        })
+        .collect()
 }
 
 pub async fn tag_detail(
@@ -300,33 +329,40 @@ pub async fn tag_detail(
         let tag_name_clone = tag_name.clone();
         let filter = extract_filter_from_params(&params, &state.clock)?;
 
-        let tracking_result =
-            tokio::task::spawn_blocking(move || parsing::process_input(&data_path, filter.as_ref()))
-                .await
-                .map_err(|e| WebError::DataProcessingFailed(format!("Task failed: {}", e)))?
-                .map_err(|e| WebError::DataProcessingFailed(e.to_string()))?;
+        let config = state.config.parsing.clone();
+        let tracking_result = tokio::task::spawn_blocking(move || {
+            parsing::process_input_with_config(&data_path, filter.as_ref(), &config)
+        })
+        .await
+        .map_err(|e| WebError::DataProcessingFailed(format!("Task failed: {}", e)))?
+        .map_err(|e| WebError::DataProcessingFailed(e.to_string()))?;
 
         if let Some(time_entries) = tracking_result.time_entries {
             let tag = Tag::from_raw(&tag_name_clone);
-            let detail_report = time_entries.tasks_tracked_for(&[tag]);
-
-            let entries: Vec<EntryDisplay> = if !detail_report.summaries().is_empty() {
-                detail_report.summaries()[0]
-                    .task_summaries()
-                    .iter()
-                    .map(|summary| EntryDisplay {
-                        description: summary.description.clone(),
-                        duration: summary.minutes,
-                    })
-                    .collect()
-            } else {
-                vec![]
-            };
+            let entries: Vec<EntryDisplay> = time_entries
+                .entries_tagged(&tag)
+                .into_iter()
+                .map(|entry| {
+                    let description = entry.description.clone().unwrap_or_default();
+                    EntryDisplay {
+                        date: entry.date.map(|date| date.to_string()).unwrap_or_default(),
+                        source: entry
+                            .source
+                            .as_ref()
+                            .map(ToString::to_string)
+                            .unwrap_or_default(),
+                        tickets: ticket_links(&entry.tickets, &state.config),
+                        description,
+                        duration: entry.minutes_for(&tag),
+                    }
+                })
+                .collect();
+            let total_minutes = entries.iter().map(|entry| entry.duration).sum();
 
             TagDetailTemplate {
                 tag_name: tag_name.clone(),
                 entries,
-                total_minutes: detail_report.total_minutes(),
+                total_minutes,
             }
         } else {
             TagDetailTemplate {
@@ -0,0 +1,148 @@
+use crate::common::*;
+
+#[test]
+fn check_should_report_errors_with_code_location_and_fix_and_fail() {
+    let content = r"## TT 2025-01-15
+- #prj-1 1h Task A
+- #prj-1 Task B";
+
+    Cmd::given()
+        .check_command()
+        .a_file_with_content(content)
+        .when_run()
+        .should_fail()
+        .expect_output("test.md:3:1: error[TT001]: missing time: - #prj-1 Task B")
+        .expect_output("fix: add a duration")
+        .expect_output("1 error, 0 warnings")
+        .expect_error("check found errors");
+}
+
+#[test]
+fn check_should_succeed_with_only_warnings() {
+    let content = r"## TT 2025-01-15 [vacay]
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .check_command()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output("test.md:1:18: warning[TT012]: unknown day annotation: [vacay]")
+        .expect_output("0 errors, 1 warning");
+}
+
+#[test]
+fn check_should_report_no_problems_for_valid_files() {
+    Cmd::given()
+        .check_command()
+        .a_file_with_content("## TT 2025-01-15\n- #prj-1 1h Task A")
+        .when_run()
+        .should_succeed()
+        .expect_output("No problems found")
+        .expect_no_text("prj-1");
+}
+
+#[test]
+fn check_should_validate_every_file_in_directory() {
+    Cmd::given()
+        .check_command()
+        .a_directory_containing_files(&[
+            ("2025/01.md", "## TT 2025-01-15\n- #prj-1 Task A"),
+            ("2025/02.md", "## TT 2025-02-03\n- #prj-1 1h Task B 1h"),
+        ])
+        .when_run()
+        .should_fail()
+        .expect_output("2025/01.md:2:1: error[TT001]")
+        .expect_output("2025/02.md:2:1: warning[TT008]: multiple durations")
+        .expect_output("1 error, 1 warning");
+}
+
+#[test]
+fn check_should_point_at_column_of_offending_token() {
+    let content = r"## TT 2025-01-15
+  - #prj-1 1h30 Task A";
+
+    Cmd::given()
+        .check_command()
+        .a_file_with_content(content)
+        .when_run()
+        .should_fail()
+        .expect_output("test.md:2:12: error[TT002]: invalid time format: 1h30");
+}
+
+#[test]
+fn check_should_point_at_offending_token_when_its_text_appears_earlier() {
+    let content = r"## TT 2025-01-15
+- #prj-1h30 1h30 Task A";
+
+    Cmd::given()
+        .check_command()
+        .a_file_with_content(content)
+        .when_run()
+        .should_fail()
+        .expect_output("test.md:2:13: error[TT002]: invalid time format: 1h30");
+}
+
+#[test]
+fn check_should_point_at_overlapping_clock_range() {
+    let content = r"## TT 2025-01-15
+- #prj-1 09:00-10:00 Task A
+- #prj-1 Task B 09:30-10:30";
+
+    Cmd::given()
+        .check_command()
+        .diagnostics_format("json")
+        .a_file_with_content(content)
+        .when_run()
+        .expect_output(r#""line":3,"start":16,"end":27,"column":17,"end_column":28"#);
+}
+
+#[test]
+fn check_should_write_json_lines_with_span() {
+    let content = r"## TT 2025-01-15
+- #prj-1 1h30 Task A";
+
+    Cmd::given()
+        .check_command()
+        .diagnostics_format("json")
+        .a_file_with_content(content)
+        .when_run()
+        .should_fail()
+        .expect_output(r#"{"code":"TT002","severity":"error","file":"test.md","line":2,"start":9,"end":13,"column":10,"end_column":14,"message":"invalid time format: 1h30""#)
+        .expect_no_text("1 error");
+}
+
+#[test]
+fn report_should_write_warnings_as_json_lines() {
+    let content = r"## TT 2025-01-15
+- #prj-1 1h Task A
+- #prj-1 ##a ##b 1h Task B";
+
+    Cmd::given()
+        .diagnostics_format("json")
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate()
+        .expect_error(r#""code":"TT004","severity":"error","file":"test.md","line":3,"start":13,"end":16,"column":14"#);
+}
+
+#[test]
+fn check_should_warn_about_entries_outside_tt_sections() {
+    let content = r"# Notes
+- #prj-1 2h Task A
+## TT 2025-01-15
+- #prj-1 1h Task B";
+
+    Cmd::given()
+        .check_command()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output(
+            "test.md:2:1: warning[TT019]: entry outside a TT section: - #prj-1 2h Task A",
+        )
+        .expect_output("fix: move the entry below a `## TT YYYY-MM-DD` header");
+}
@@ -136,6 +136,7 @@ impl StatsCommandResult {
         self
     }
 
+    #[allow(dead_code)]
     pub fn should_fail(mut self) -> Self {
         self.output = self.output.failure();
         self
@@ -168,6 +169,7 @@ impl StatsAssertion {
         self
     }
 
+    #[allow(dead_code)]
     pub fn with_failure(self) -> Self {
         let success = self
             .record
@@ -205,6 +207,7 @@ impl StatsAssertion {
         self
     }
 
+    #[allow(dead_code)]
     pub fn having_flag(self, flag: &str) -> Self {
         let flags_used = self
             .record
@@ -214,7 +217,7 @@ impl StatsAssertion {
 
         let has_flag = flags_used
             .iter()
-            .any(|v| v.as_str().map_or(false, |s| s == flag));
+            .any(|v| v.as_str().is_some_and(|s| s == flag));
 
         assert!(has_flag, "Flag '{}' should be in flags_used", flag);
         self
@@ -230,7 +233,7 @@ impl StatsAssertion {
         for expected_flag in flags {
             let has_flag = flags_used
                 .iter()
-                .any(|v| v.as_str().map_or(false, |s| s == expected_flag));
+                .any(|v| v.as_str().is_some_and(|s| s == expected_flag));
             assert!(
                 has_flag,
                 "Flag '{}' should be in flags_used",
@@ -249,7 +252,7 @@ impl StatsAssertion {
 
         let has_flag = flags_used
             .iter()
-            .any(|v| v.as_str().map_or(false, |s| s == flag));
+            .any(|v| v.as_str().is_some_and(|s| s == flag));
 
         assert!(!has_flag, "Flag '{}' should not be in flags_used", flag);
         self
@@ -21,6 +21,10 @@ impl CommandArgs {
         self.args.push(format!("--{flag}"));
     }
 
+    fn add_command(&mut self, command: &str) {
+        self.args.push(command.to_string());
+    }
+
     fn add_option(&mut self, option: &str, value: &str) {
         self.args.push(format!("--{option}"));
         self.args.push(value.to_string());
@@ -70,6 +74,7 @@ impl InputSource {
 struct ExecutionContext {
     command: Command,
     _temp_dir: Option<Arc<assert_fs::TempDir>>,
+    _config_dir: Option<assert_fs::TempDir>,
 }
 
 impl ExecutionContext {
@@ -98,6 +103,7 @@ impl Cmd {
 pub struct CommandSpec {
     args: CommandArgs,
     input: Option<InputSource>,
+    config: Option<String>,
     run_date: Option<NaiveDate>,
 }
 
@@ -112,6 +118,7 @@ impl CommandSpec {
         Self {
             args: CommandArgs::new(),
             input: None,
+            config: None,
             run_date: None,
         }
     }
@@ -175,6 +182,71 @@ impl CommandSpec {
         self
     }
 
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.args.add_option("depth", &depth.to_string());
+        self
+    }
+
+    pub fn metadata_filter(mut self, pairs: &[&str]) -> Self {
+        self.args.add_option("meta", &pairs.join(","));
+        self
+    }
+
+    pub fn group_by(mut self, key: &str) -> Self {
+        self.args.add_option("group-by", key);
+        self
+    }
+
+    pub fn by_ticket_flag(mut self) -> Self {
+        self.args.add_flag("by-ticket");
+        self
+    }
+
+    pub fn check_command(mut self) -> Self {
+        self.args.add_command("check");
+        self
+    }
+
+    pub fn diagnostics_format(mut self, format: &str) -> Self {
+        self.args.add_option("diagnostics", format);
+        self
+    }
+
+    pub fn strict_flag(mut self) -> Self {
+        self.args.add_flag("strict");
+        self
+    }
+
+    pub fn log_flag(mut self) -> Self {
+        self.args.add_flag("log");
+        self
+    }
+
+    pub fn estimates_flag(mut self) -> Self {
+        self.args.add_flag("estimates");
+        self
+    }
+
+    pub fn people_flag(mut self) -> Self {
+        self.args.add_flag("people");
+        self
+    }
+
+    pub fn exclude_leave_flag(mut self) -> Self {
+        self.args.add_flag("exclude-leave");
+        self
+    }
+
+    pub fn person_filter(mut self, name: &str) -> Self {
+        self.args.add_option("person", name);
+        self
+    }
+
+    pub fn daily_notes_flag(mut self) -> Self {
+        self.args.add_flag("daily-notes");
+        self
+    }
+
     pub fn a_directory_containing_files(mut self, files: &[(&str, &str)]) -> Self {
         let files = files
             .iter()
@@ -190,6 +262,11 @@ impl CommandSpec {
         self
     }
 
+    pub fn a_config_file_with_content(mut self, content: &str) -> Self {
+        self.config = Some(content.to_string());
+        self
+    }
+
     pub fn at_date(mut self, date: &str) -> Self {
         let date =
             NaiveDate::parse_from_str(date, "%Y-%m-%d").expect("Invalid date format in test");
@@ -245,11 +322,22 @@ impl CommandSpec {
             command.arg("--input").arg(input_path);
             temp_dir
         });
+        let config_dir = self.config.map(|content| {
+            let config_dir =
+                assert_fs::TempDir::new().expect("Failed to create temporary directory");
+            let config_file = config_dir.child("tt.json");
+            config_file
+                .write_str(&content)
+                .expect("Failed to write to config file");
+            command.arg("--config").arg(config_file.path());
+            config_dir
+        });
         command.args(self.args.clone().into_vec());
 
         let mut context = ExecutionContext {
             command,
             _temp_dir: temp_dir,
+            _config_dir: config_dir,
         };
         if let Some(run_date) = self.run_date {
             context.run_on_date(run_date);
@@ -392,6 +480,12 @@ impl CommandResult {
         }
     }
 
+    pub fn expect_no_error(self, text: &str) -> Self {
+        Self {
+            output: self.output.stderr(predicate::str::contains(text).not()),
+        }
+    }
+
     pub fn expect_no_text(self, text: &str) -> Self {
         Self {
             output: self.output.stdout(predicate::str::contains(text).not()),
@@ -0,0 +1,81 @@
+use crate::common::*;
+
+#[test]
+fn daily_notes_should_date_entries_by_file_name() {
+    Cmd::given()
+        .daily_notes_flag()
+        .a_directory_containing_files(&[
+            ("2025-01-15.md", "- #prj-1 1h Task A"),
+            ("journals/2025_01_16.md", "# Standup\n- #prj-1 2h Task B"),
+        ])
+        .when_run()
+        .should_succeed()
+        .expect_start_date("2025-01-15")
+        .expect_end_date("2025-01-16")
+        .expect_project("prj-1")
+        .taking("3h 00m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn daily_notes_should_prefer_front_matter_date() {
+    let content = r"---
+title: Planning
+date: 2025-01-20
+---
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .daily_notes_flag()
+        .a_directory_containing_files(&[("planning.md", content)])
+        .when_run()
+        .should_succeed()
+        .expect_start_date("2025-01-20")
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate();
+}
+
+#[test]
+fn daily_notes_should_give_explicit_headers_priority() {
+    let content = r"- #prj-1 1h Task A
+## TT 2025-01-10
+- #prj-2 2h Task B
+## Notes
+- #prj-1 30m Task C";
+
+    Cmd::given()
+        .daily_notes_flag()
+        .a_directory_containing_files(&[("2025-01-15.md", content)])
+        .when_run()
+        .should_succeed()
+        .expect_start_date("2025-01-10")
+        .expect_end_date("2025-01-15")
+        .expect_project("prj-1")
+        .taking("1h 30m")
+        .expect_project("prj-2")
+        .taking("2h 00m")
+        .validate();
+}
+
+#[test]
+fn daily_notes_should_be_enabled_by_config() {
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "parsing": { "daily-notes": true } }"#)
+        .a_directory_containing_files(&[("2025-01-15.md", "- #prj-1 1h Task A")])
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate();
+}
+
+#[test]
+fn notes_without_tt_header_should_be_ignored_by_default() {
+    Cmd::given()
+        .a_directory_containing_files(&[("2025-01-15.md", "- #prj-1 1h Task A")])
+        .when_run()
+        .should_succeed()
+        .expect_no_data_found();
+}
@@ -0,0 +1,84 @@
+use crate::common::*;
+
+const CONTENT: &str = r"<!-- tt: metadata-keys = location -->
+## TT 2025-01-13 {location:office}
+- #prj-1 4h Task A
+## TT 2025-01-14 [holiday]
+- #prj-1 1h Hotfix
+## TT 2025-01-15 [half-day] {location:home}
+- #prj-1 2h Task B
+- #prj-2 1h Task C location:office
+## TT 2025-01-16 [sick]
+## TT 2025-01-17 {location:home}
+- #prj-2 3h Task D";
+
+#[test]
+fn overview_should_count_working_days_without_leave() {
+    Cmd::given()
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("4 days, 2.8 h/day, 11h 00m total")
+        .expect_output("Working days: 2.5 (1 holiday, 1 sick day, 1 half day)")
+        .expect_no_warnings();
+}
+
+#[test]
+fn overview_should_not_show_working_days_without_leave() {
+    let content = r"## TT 2025-01-13 {location:office}
+- #prj-1 4h Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_no_text("Working days");
+}
+
+#[test]
+fn exclude_leave_should_leave_out_entries_on_holidays_and_sick_days() {
+    Cmd::given()
+        .exclude_leave_flag()
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("3 days, 3.3 h/day, 10h 00m total")
+        .expect_project("prj-1")
+        .taking("6h 00m")
+        .validate()
+        .expect_no_text("Hotfix");
+}
+
+#[test]
+fn group_by_location_should_use_location_of_the_day() {
+    Cmd::given()
+        .group_by("location")
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("home.................. 5h 00m")
+        .expect_output("office................ 5h 00m")
+        .expect_output("<no location>......... 1h 00m");
+}
+
+#[test]
+fn markdown_overview_should_list_working_days() {
+    Cmd::given()
+        .output_format("markdown")
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("- **Working Days**: 2.5 (1 holiday, 1 sick day, 1 half day)");
+}
+
+#[test]
+fn header_should_warn_about_unknown_day_annotation() {
+    let content = r"## TT 2025-01-13 [vacay]
+- #prj-1 4h Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(1, r"unknown day annotation: \[vacay\]");
+}
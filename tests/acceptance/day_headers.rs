@@ -0,0 +1,96 @@
+use crate::common::*;
+
+#[test]
+fn header_should_accept_weekday_suffix() {
+    let content = r"## TT 2025-01-15 Wed
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn header_should_accept_iso_week_date() {
+    let content = r"# TT 2025-W03-3
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_start_date("2025-01-15")
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate();
+}
+
+#[test]
+fn header_should_accept_configured_date_format() {
+    let content = r"# TT 15/01/2025
+- #prj-1 1h Task A
+# TT 2025-01-16
+- #prj-1 2h Task B";
+
+    Cmd::given()
+        .a_config_file_with_content(
+            r#"{ "parsing": { "date-formats": ["%d/%m/%Y", "%Y-%m-%d"] } }"#,
+        )
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_start_date("2025-01-15")
+        .expect_end_date("2025-01-16")
+        .expect_project("prj-1")
+        .taking("3h 00m")
+        .validate();
+}
+
+#[test]
+fn header_should_accept_configured_date_format_with_spaces() {
+    let content = r"# TT 15 Jan 2025 Wed [half-day]
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "parsing": { "date-formats": ["%d %b %Y"] } }"#)
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_start_date("2025-01-15")
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn header_should_be_invalid_when_format_not_configured() {
+    let content = r"# TT 15/01/2025
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(1, "invalid date format: 15/01/2025");
+}
+
+#[test]
+fn header_should_warn_when_weekday_does_not_match_date() {
+    let content = r"## TT 2025-01-15 Tue
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(1, "weekday does not match date: 2025-01-15 is Wed, not Tue")
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate();
+}
@@ -0,0 +1,109 @@
+use rstest::rstest;
+
+use crate::common::Cmd;
+
+#[rstest]
+#[case("1.5h", "1h 30m")]
+#[case("1h30m", "1h 30m")]
+#[case("90min", "1h 30m")]
+#[case("2hrs", "2h 00m")]
+fn duration_should_accept_decimal_compound_and_long_units(
+    #[case] duration: &str,
+    #[case] expected: &str,
+) {
+    let content = format!(
+        r"## TT 2020-01-01
+- #prj-1 {duration} Task A"
+    );
+
+    Cmd::given()
+        .a_file_with_content(&content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking(expected)
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn duration_should_warn_when_ambiguous() {
+    let content = r"## TT 2020-01-01
+- #prj-1 1h30 Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(2, "invalid time format: 1h30");
+}
+
+#[test]
+fn day_unit_should_stay_description_without_day_length() {
+    let content = r"## TT 2020-01-01
+- #prj-1 1h research 3d printing";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn day_unit_should_use_configured_day_length() {
+    let content = r"## TT 2020-01-01
+- #prj-1 1d Task A";
+
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "parsing": { "day-length": "7h30m" } }"#)
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("7h 30m")
+        .validate();
+}
+
+#[test]
+fn units_should_use_configured_lengths_and_be_noted_in_report() {
+    let content = r"## TT 2020-01-01
+- #prj-1 2p Task A
+- #prj-2 2block Task B";
+
+    Cmd::given()
+        .a_config_file_with_content(
+            r#"{ "parsing": { "pomodoro-length": "25m", "units": { "block": "45m" } } }"#,
+        )
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("0h 50m")
+        .expect_project("prj-2")
+        .taking("1h 30m")
+        .validate()
+        .expect_output("Units: p = 25m, block = 45m");
+}
+
+#[test]
+fn report_should_not_note_default_units() {
+    Cmd::given()
+        .a_file_with_content("## TT 2020-01-01\n- #prj-1 2p Task A")
+        .when_run()
+        .should_succeed()
+        .expect_no_text("Units:");
+}
+
+#[test]
+fn app_should_fail_when_config_invalid() {
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "parsing": { "day-length": "long" } }"#)
+        .a_file_with_content("## TT 2020-01-01\n- #prj-1 1d")
+        .when_run()
+        .should_fail()
+        .expect_error("invalid configuration");
+}
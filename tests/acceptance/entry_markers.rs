@@ -0,0 +1,105 @@
+use crate::common::*;
+
+#[test]
+fn entry_markers_should_only_accept_dash_by_default() {
+    let content = r"## TT 2020-01-01
+- #prj-1 1h Task A
+* #prj-1 2h Task B
++ #prj-1 3h Task C
+1. #prj-1 4h Task D";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate();
+}
+
+#[test]
+fn entry_markers_should_accept_configured_markers() {
+    let content = r"## TT 2020-01-01
+- #prj-1 1h Task A
+* #prj-1 2h Task B
++ #prj-1 3h Task C
+1. #prj-1 4h Task D
+2) #prj-1 5h Task E";
+
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "parsing": { "entry-markers": ["*", "+", "numbered"] } }"#)
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("14h 00m")
+        .validate();
+}
+
+#[test]
+fn entry_markers_should_fail_when_marker_is_unknown() {
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "parsing": { "entry-markers": ["="] } }"#)
+        .a_file_with_content("## TT 2020-01-01\n- #prj-1 1h")
+        .when_run()
+        .should_fail()
+        .expect_error("invalid configuration");
+}
+
+#[test]
+fn checkboxes_should_be_entries() {
+    let content = r"## TT 2020-01-01
+- [x] #prj-1 1h Task A
+- [ ] #prj-1 2h Task B";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("3h 00m")
+        .validate();
+}
+
+#[test]
+fn report_should_split_done_and_open_tasks() {
+    let content = r"## TT 2020-01-01
+- [x] #prj-1 1h Task A
+- [X] #prj-2 30m Task B
+- [ ] #prj-1 2h Task C
+- #prj-2 30m Task D";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output("Tasks:")
+        .expect_output("* done.................. 1h 30m ( 38%)")
+        .expect_output("* open.................. 2h 00m ( 50%)");
+}
+
+#[test]
+fn report_should_not_show_tasks_when_no_checkboxes_are_present() {
+    let content = r"## TT 2020-01-01
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_no_text("Tasks:");
+}
+
+#[test]
+fn markdown_report_should_split_done_and_open_tasks() {
+    let content = r"## TT 2020-01-01
+- [x] #prj-1 1h Task A
+- [ ] #prj-1 1h Task B";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .output_format("markdown")
+        .when_run()
+        .should_succeed()
+        .expect_output("### Tasks\n- **done**:  1h 00m (50%)\n- **open**:  1h 00m (50%)");
+}
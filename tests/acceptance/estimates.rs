@@ -0,0 +1,88 @@
+use crate::common::*;
+
+const CONTENT: &str = r"## TT 2020-01-01
+- #prj-1 3h est:3h Task A
+- #prj-1 1h est:2h Task B
+- #prj-2 1h Task C
+## TT 2020-01-02
+- #prj-1 30m est:3h Task A
+- #prj-2 2h est:2h Task D";
+
+#[test]
+fn estimates_should_show_estimate_actual_and_variance_per_task() {
+    Cmd::given()
+        .estimates_flag()
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("Project: prj-1")
+        .expect_output("- Task A................ 3h 30m of 3h 00m estimated, +0h 30m (17% over)")
+        .expect_output("- Task B................ 1h 00m of 2h 00m estimated, -1h 00m (50% under)")
+        .expect_output("Project: prj-2")
+        .expect_output("- Task D................ 2h 00m of 2h 00m estimated, +0h 00m (on estimate)")
+        .expect_no_text("Task C");
+}
+
+#[test]
+fn estimates_should_only_include_filtered_tags() {
+    Cmd::given()
+        .estimates_flag()
+        .tags_filter(&["prj-2"])
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("Task D")
+        .expect_no_text("prj-1");
+}
+
+#[test]
+fn estimates_should_be_a_markdown_table() {
+    Cmd::given()
+        .estimates_flag()
+        .output_format("markdown")
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("# Estimates Report")
+        .expect_output("## Project: prj-1")
+        .expect_output("| Task A | 3h 00m | 3h 30m | +0h 30m (17% over) |");
+}
+
+#[test]
+fn estimates_should_report_when_no_task_is_estimated() {
+    Cmd::given()
+        .estimates_flag()
+        .a_file_with_content("## TT 2020-01-01\n- #prj-1 1h Task A")
+        .when_run()
+        .should_succeed()
+        .expect_output("No estimates found.");
+}
+
+#[test]
+fn estimates_should_fail_when_combined_with_details() {
+    Cmd::given()
+        .estimates_flag()
+        .details_flag()
+        .tags_filter(&["prj-1"])
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_fail()
+        .expect_error("--estimates cannot be combined with --details");
+}
+
+#[test]
+fn estimate_that_is_not_a_duration_should_be_warned_about_and_keep_time() {
+    let content = r"## TT 2020-01-01
+- #prj-1 1h Task A
+- #prj-2 1h est:soon Task B";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-2")
+        .taking("1h 00m")
+        .validate()
+        .expect_output("2h 00m total")
+        .expect_warning_at_line(3, "invalid estimate: est:soon");
+}
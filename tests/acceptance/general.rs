@@ -331,7 +331,7 @@ mod error_reporting {
     }
 
     #[test]
-    fn app_should_only_warn_for_tt_sections() {
+    fn app_should_warn_about_entries_after_non_date_header() {
         let content = r"## A section title without teetee in
         - #dev 1h Task1";
 
@@ -339,7 +339,33 @@ mod error_reporting {
             .a_file_with_content(content)
             .when_run()
             .should_succeed()
-            .expect_no_warnings();
+            .expect_no_data_found()
+            .expect_warning_at_line(2, "entry outside a TT section: - #dev 1h Task1");
+    }
+
+    #[test]
+    fn app_should_warn_about_entries_before_first_tt_header() {
+        let content = r"- #prj-x 1h forgot the header
+## TT 2020-01-01
+- #dev 1h Task1
+## Notes
+- #prj-y 30m after the day ended";
+
+        Cmd::given()
+            .a_file_with_content(content)
+            .when_run()
+            .should_succeed()
+            .expect_project("dev")
+            .taking("1h 00m")
+            .validate()
+            .expect_warning_at_line(
+                1,
+                "entry outside a TT section: - #prj-x 1h forgot the header",
+            )
+            .expect_warning_at_line(
+                5,
+                "entry outside a TT section: - #prj-y 30m after the day ended",
+            );
     }
 
     #[test]
@@ -0,0 +1,145 @@
+use crate::common::*;
+
+#[test]
+fn sub_heading_should_keep_date_and_provide_tags() {
+    let content = r"## TT 2020-01-01
+### #prj-alpha
+- 1h Task A
+- #design 30m Task B
+### #prj-beta
+- 2h Task C";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-alpha")
+        .taking("1h 30m")
+        .expect_project("prj-beta")
+        .taking("2h 00m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn sub_heading_tags_should_be_inherited_by_filtered_entries() {
+    let content = r"## TT 2020-01-01
+### #prj-alpha
+- #design 30m Task B
+- #dev 1h Task C";
+
+    Cmd::given()
+        .tags_filter(&["design"])
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-alpha")
+        .taking("0h 30m")
+        .validate();
+}
+
+#[test]
+fn sub_heading_should_provide_outcome() {
+    let content = r"## TT 2020-01-01
+### #prj-alpha ##launch
+- 1h Task A
+- ##support 30m Task B";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_outcome_with_duration("launch", "1h 00m")
+        .expect_outcome_with_duration("support", "0h 30m");
+}
+
+#[test]
+fn nested_sub_headings_should_combine_tags() {
+    let content = r"## TT 2020-01-01
+### #client
+#### #acme
+- 1h Task A
+#### #globex
+- 2h Task B";
+
+    Cmd::given()
+        .tags_filter(&["acme"])
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("client")
+        .taking("1h 00m")
+        .validate();
+}
+
+#[test]
+fn sub_heading_without_tags_should_keep_date() {
+    let content = r"## TT 2020-01-01
+### Morning
+- #prj-1 1h Task A
+### Afternoon
+- #prj-1 2h Task B";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("3h 00m")
+        .validate();
+}
+
+#[test]
+fn header_at_tt_level_should_end_day() {
+    let content = r"## TT 2020-01-01
+### #prj-alpha
+- 1h Task A
+## Notes
+- #prj-1 2h not tracked";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-alpha")
+        .taking("1h 00m")
+        .validate()
+        .expect_no_text("prj-1");
+}
+
+#[test]
+fn entry_below_tagged_sub_heading_should_still_need_time() {
+    let content = r"## TT 2020-01-01
+### #prj-alpha
+- #design Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(3, "missing time: - #design Task A");
+}
+
+#[test]
+fn note_below_tagged_sub_heading_should_not_be_an_entry() {
+    let content = r"## TT 2020-01-01
+### #prj-alpha
+- remember to ping Bob
+- 1h Task A";
+
+    Cmd::given()
+        .check_command()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output("No problems found");
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-alpha")
+        .taking("1h 00m")
+        .validate()
+        .expect_no_warnings();
+}
@@ -0,0 +1,158 @@
+use crate::common::*;
+
+#[test]
+fn log_should_list_entries_in_date_order_with_file_and_line() {
+    Cmd::given()
+        .log_flag()
+        .a_directory_containing_files(&[
+            (
+                "2025/02.md",
+                "## TT 2025-02-03\n- #prj-1 2h Task C\n- #prj-2 30m Task D",
+            ),
+            (
+                "2025/01.md",
+                "# Notes\n## TT 2025-01-15\n- #prj-1 1h Task A",
+            ),
+        ])
+        .when_run()
+        .should_succeed()
+        .expect_output("Log\n2025-01-15 -> 2025-02-03\nTotal:  3h 30m")
+        .expect_output(
+            "2025-01-15 1h 00m #prj-1 Task A (2025/01.md:3)\n\
+             2025-02-03 2h 00m #prj-1 Task C (2025/02.md:2)\n\
+             2025-02-03 0h 30m #prj-2 Task D (2025/02.md:3)",
+        );
+}
+
+#[test]
+fn log_should_only_list_matching_entries() {
+    let content = r"## TT 2025-01-15
+- #prj-1 1h Task A
+- #prj-2 2h Task B";
+
+    Cmd::given()
+        .log_flag()
+        .tags_filter(&["prj-2"])
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output("2025-01-15 2h 00m #prj-2 Task B (test.md:3)")
+        .expect_no_text("Task A");
+}
+
+#[test]
+fn log_should_separate_date_and_duration_of_long_entries() {
+    Cmd::given()
+        .log_flag()
+        .a_file_with_content("## TT 2025-01-15\n- #prj-1 10h Task A")
+        .when_run()
+        .should_succeed()
+        .expect_output("2025-01-15 10h 00m #prj-1 Task A (test.md:2)");
+}
+
+#[test]
+fn log_should_show_tags_and_outcome_of_entries() {
+    let content = r"## TT 2025-01-15
+- #prj-1 #meeting ##launch 1h Planning";
+
+    Cmd::given()
+        .log_flag()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output("2025-01-15 1h 00m #prj-1 #meeting ##launch Planning (test.md:2)");
+}
+
+#[test]
+fn markdown_log_should_be_a_table_of_entries() {
+    let content = r"## TT 2025-01-15
+- #prj-1 ##launch 1h Task A
+- #prj-2 30m Task B";
+
+    Cmd::given()
+        .log_flag()
+        .output_format("markdown")
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output("# Log")
+        .expect_output("| Date | Duration | Tags | Outcome | Description | Source |")
+        .expect_output("| 2025-01-15 | 1h 00m | #prj-1 | ##launch | Task A | `test.md:2` |")
+        .expect_output("| 2025-01-15 | 0h 30m | #prj-2 |  | Task B | `test.md:3` |");
+}
+
+#[test]
+fn log_should_show_all_weighted_outcomes_of_entries() {
+    let content = r"## TT 2025-01-15
+- #prj-1 ##launch:70% ##hiring:30% 1h Task A
+- #prj-2 ##launch*1 ##hiring*1 1h Task B";
+
+    Cmd::given()
+        .log_flag()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output("2025-01-15 1h 00m #prj-1 ##launch:70% ##hiring:30% Task A (test.md:2)")
+        .expect_output("2025-01-15 1h 00m #prj-2 ##launch*1 ##hiring*1 Task B (test.md:3)");
+}
+
+#[test]
+fn markdown_log_should_show_all_weighted_outcomes_of_entries() {
+    let content = r"## TT 2025-01-15
+- #prj-1 ##launch:70% ##hiring:30% 1h Task A";
+
+    Cmd::given()
+        .log_flag()
+        .output_format("markdown")
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output(
+            "| 2025-01-15 | 1h 00m | #prj-1 | ##launch:70% ##hiring:30% | Task A | `test.md:2` |",
+        );
+}
+
+#[test]
+fn json_format_should_include_date_file_and_line_of_entries() {
+    let content = r"## TT 2025-01-15
+- #prj-1 ##launch 1h Task A";
+
+    Cmd::given()
+        .log_flag()
+        .output_format("json")
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output(r#""report": "log""#)
+        .expect_output(r#""date": "2025-01-15""#)
+        .expect_output(r#""file": "test.md""#)
+        .expect_output(r#""line": 2"#)
+        .expect_output(r#""outcome": "launch""#);
+}
+
+#[test]
+fn json_format_should_output_overview_totals() {
+    let content = r"## TT 2025-01-15
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .output_format("json")
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output(r#""report": "overview""#)
+        .expect_output(r#""description": "prj-1""#)
+        .expect_output(r#""total_minutes": 60"#);
+}
+
+#[test]
+fn log_should_not_be_combined_with_details() {
+    Cmd::given()
+        .log_flag()
+        .details_flag()
+        .tags_filter(&["prj-1"])
+        .a_file_with_content("## TT 2025-01-15\n- #prj-1 1h Task A")
+        .when_run()
+        .should_fail()
+        .expect_error("--log cannot be combined with --details");
+}
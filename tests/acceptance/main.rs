@@ -1,11 +1,32 @@
 mod breakdown;
+mod check;
 mod cli_stats;
 mod cli_stats_common;
 mod common;
+mod daily_notes;
+mod day_annotations;
+mod day_headers;
 mod details;
+mod durations;
+mod entry_markers;
+mod estimates;
 mod general;
+mod header_scopes;
 mod limit;
+mod log;
 mod markdown;
+mod markdown_blocks;
+mod metadata;
 mod outcomes;
+mod people;
 mod periods;
+mod rules;
+mod strict;
+mod subtasks;
+mod tag_grammar;
+mod tag_hierarchy;
 mod tags;
+mod tickets;
+mod time_markers;
+mod time_ranges;
+mod weights;
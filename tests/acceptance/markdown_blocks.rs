@@ -0,0 +1,84 @@
+use rstest::rstest;
+
+use crate::common::Cmd;
+
+#[rstest]
+#[case("```bash")]
+#[case("~~~")]
+#[case("````markdown")]
+fn fenced_code_should_not_change_day_or_add_entries(#[case] fence: &str) {
+    let closing_fence: String = fence.chars().take_while(|c| !c.is_alphabetic()).collect();
+    let content = format!(
+        r"## TT 2020-01-01
+- #dev 1h Task A
+{fence}
+# TT 2021-06-06
+- #dev 5h snippet
+{closing_fence}
+- #dev 30m Task B"
+    );
+
+    Cmd::given()
+        .a_file_with_content(&content)
+        .when_run()
+        .should_succeed()
+        .expect_output("2020-01-01 -> 2020-01-01")
+        .expect_project("dev")
+        .taking("1h 30m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn html_comments_should_be_ignored() {
+    let content = r"## TT 2020-01-01
+- #dev 1h Task A
+<!-- - #dev 2h single line -->
+<!--
+- #dev 4h commented out
+## TT 2021-06-06
+-->
+- #dev 30m Task B";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output("2020-01-01 -> 2020-01-01")
+        .expect_project("dev")
+        .taking("1h 30m")
+        .validate();
+}
+
+#[test]
+fn blockquotes_should_be_ignored() {
+    let content = r"## TT 2020-01-01
+- #dev 1h Task A
+> - #dev 2h quoted
+> # TT 2021-06-06";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output("2020-01-01 -> 2020-01-01")
+        .expect_project("dev")
+        .taking("1h 00m")
+        .validate();
+}
+
+#[test]
+fn directives_should_apply_next_to_comments() {
+    let content = r"<!-- tt: time-marker = first -->
+<!-- a regular comment -->
+## TT 2020-01-01
+- #dev 1h fix 2h timeout bug";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("dev")
+        .taking("1h 00m")
+        .validate();
+}
@@ -0,0 +1,118 @@
+use crate::common::*;
+
+const CONTENT: &str = r"## TT 2020-01-01
+- #prj-1 1h client:acme billable:yes Task A
+- #prj-2 2h client:acme billable:no Task B
+- #prj-1 30m client:globex Task C
+- #prj-3 15m Task D";
+
+const CONFIG: &str = r#"{ "parsing": { "metadata-keys": ["client", "billable"] } }"#;
+
+#[test]
+fn metadata_should_not_end_up_in_description() {
+    Cmd::given()
+        .a_config_file_with_content(CONFIG)
+        .details_flag()
+        .tags_filter(&["prj-1"])
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_task_with_duration("Task A", "1h 00m")
+        .expect_no_text("client:acme");
+}
+
+#[test]
+fn metadata_filter_should_only_include_matching_entries() {
+    Cmd::given()
+        .a_config_file_with_content(CONFIG)
+        .metadata_filter(&["client=acme"])
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .expect_project("prj-2")
+        .taking("2h 00m")
+        .validate()
+        .expect_no_text("prj-3");
+}
+
+#[test]
+fn metadata_filters_should_all_match() {
+    Cmd::given()
+        .a_config_file_with_content(CONFIG)
+        .metadata_filter(&["client=acme", "billable=no"])
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-2")
+        .taking("2h 00m")
+        .validate()
+        .expect_no_text("prj-1");
+}
+
+#[test]
+fn metadata_filter_should_fail_when_not_key_value() {
+    Cmd::given()
+        .a_config_file_with_content(CONFIG)
+        .metadata_filter(&["client"])
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_fail()
+        .expect_error("--meta expects KEY=VALUE pairs: client");
+}
+
+#[test]
+fn overview_should_group_by_metadata_key() {
+    Cmd::given()
+        .a_config_file_with_content(CONFIG)
+        .group_by("client")
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("acme.................. 3h 00m ( 80%)")
+        .expect_output("globex................ 0h 30m ( 13%)")
+        .expect_output("<no client>........... 0h 15m (  7%)");
+}
+
+#[test]
+fn details_should_group_by_metadata_key() {
+    Cmd::given()
+        .a_config_file_with_content(CONFIG)
+        .details_flag()
+        .group_by("client")
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("Project: acme")
+        .expect_output("Project: globex")
+        .expect_task_with_duration("Task B", "2h 00m")
+        .expect_task_with_duration("Task C", "0h 30m");
+}
+
+#[test]
+fn group_by_should_fail_with_depth() {
+    Cmd::given()
+        .a_config_file_with_content(CONFIG)
+        .group_by("client")
+        .depth(2)
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_fail()
+        .expect_error("--group-by cannot be combined with --depth");
+}
+
+#[test]
+fn metadata_should_stay_in_description_when_key_not_configured() {
+    let content = r"## TT 2020-01-01
+- #prj-1 1h TODO:fix client:acme";
+
+    Cmd::given()
+        .details_flag()
+        .tags_filter(&["prj-1"])
+        .a_config_file_with_content(r#"{ "parsing": { "metadata-keys": ["billable"] } }"#)
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_task_with_duration("TODO:fix client:acme", "1h 00m");
+}
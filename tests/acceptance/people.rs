@@ -0,0 +1,73 @@
+use crate::common::*;
+
+const CONTENT: &str = r"## TT 2020-01-01
+- #prj-1 1h pairing with @alice
+- #prj-2 2h review with @alice and @bob
+- #prj-1 30m planning with @Bob
+- #prj-3 30m focus time";
+
+#[test]
+fn people_should_total_time_per_person() {
+    Cmd::given()
+        .people_flag()
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("@alice................ 3h 00m")
+        .expect_output("@bob.................. 2h 30m")
+        .expect_no_text("focus time");
+}
+
+#[test]
+fn people_details_should_list_tasks_per_person() {
+    Cmd::given()
+        .people_flag()
+        .details_flag()
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("Project: @alice")
+        .expect_output("Project: @bob")
+        .expect_task_with_duration("pairing with @alice", "1h 00m");
+}
+
+#[test]
+fn person_filter_should_only_include_entries_mentioning_person() {
+    Cmd::given()
+        .person_filter("bob")
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-2")
+        .taking("2h 00m")
+        .expect_project("prj-1")
+        .taking("0h 30m")
+        .validate()
+        .expect_no_text("prj-3");
+}
+
+#[test]
+fn person_filter_should_accept_leading_at_sign() {
+    Cmd::given()
+        .person_filter("@alice")
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-2")
+        .taking("2h 00m")
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate()
+        .expect_no_text("prj-3");
+}
+
+#[test]
+fn people_should_fail_when_combined_with_group_by() {
+    Cmd::given()
+        .people_flag()
+        .group_by("client")
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_fail()
+        .expect_error("--people cannot be combined with --group-by");
+}
@@ -0,0 +1,121 @@
+use crate::common::*;
+
+#[test]
+fn day_totalling_more_than_24_hours_should_be_warned_about() {
+    let content = r"## TT 2025-01-15
+- #prj-1 20h Task A
+- #prj-2 6h Task B";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("20h 00m")
+        .validate()
+        .expect_warning_at_line(2, "implausible day: 2025-01-15 totals 26h, more than 24h");
+}
+
+#[test]
+fn day_totalling_more_than_24_hours_should_be_warned_about_when_filtered() {
+    let content = r"## TT 2025-01-15
+- #prj-1 20h Task A
+- #prj-2 6h Task B";
+
+    Cmd::given()
+        .tags_filter(&["prj-2"])
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-2")
+        .taking("6h 00m")
+        .validate()
+        .expect_warning_at_line(2, "implausible day: 2025-01-15 totals 26h, more than 24h");
+
+    Cmd::given()
+        .tags_filter(&["prj-2"])
+        .strict_flag()
+        .a_file_with_content(content)
+        .when_run()
+        .should_fail()
+        .expect_error("implausible day: 2025-01-15 totals 26h, more than 24h");
+}
+
+#[test]
+fn entry_longer_than_16_hours_should_be_warned_about() {
+    Cmd::given()
+        .a_file_with_content("## TT 2025-01-15\n- #prj-1 40h Task A")
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(2, "implausible entry: 40h, more than 16h");
+}
+
+#[test]
+fn same_line_twice_on_a_date_should_be_warned_about() {
+    let content = r"## TT 2025-01-15
+- #prj-1 1h Task A
+- #prj-1 1h Task B
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(4, "duplicate entry: same as line 2");
+}
+
+#[test]
+fn entries_after_today_should_be_warned_about() {
+    Cmd::given()
+        .at_date("2025-01-14")
+        .a_file_with_content("## TT 2025-01-15\n- #prj-1 1h Task A")
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(2, "date in the future: 2025-01-15");
+}
+
+#[test]
+fn weekend_work_should_only_be_warned_about_when_enabled() {
+    let content = r"## TT 2025-01-18
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_no_warnings();
+
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "rules": { "weekends": true } }"#)
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(2, "work on a weekend: 2025-01-18 is Sat");
+}
+
+#[test]
+fn rule_limits_should_be_configurable() {
+    let content = r"## TT 2025-01-15
+- #prj-1 40h Task A";
+
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "rules": { "max-day": "48h", "max-entry": null } }"#)
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_no_warnings();
+}
+
+#[test]
+fn check_should_report_rule_findings_with_their_codes() {
+    let content = r"## TT 2025-01-15
+- #prj-1 1h Task A
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .check_command()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output("test.md:3:1: warning[TT022]: duplicate entry: same as line 2");
+}
@@ -0,0 +1,78 @@
+use crate::common::*;
+
+const CONTENT_WITH_WARNING: &str = r"## TT 2025-01-15
+- #prj-1 1h Task A
+- #prj-1 1h Task B 30m";
+
+#[test]
+fn strict_flag_should_fail_with_problems_instead_of_report() {
+    Cmd::given()
+        .strict_flag()
+        .a_file_with_content(CONTENT_WITH_WARNING)
+        .when_run()
+        .should_fail()
+        .expect_error("test.md:3:1: warning[TT008]: multiple durations: - #prj-1 1h Task B 30m")
+        .expect_error("0 errors, 1 warning")
+        .expect_error("strict mode: 1 problem found")
+        .expect_no_text("prj-1");
+}
+
+#[test]
+fn strict_flag_should_report_when_there_are_no_problems() {
+    Cmd::given()
+        .strict_flag()
+        .a_file_with_content("## TT 2025-01-15\n- #prj-1 1h Task A")
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate();
+}
+
+#[test]
+fn strict_mode_should_be_read_from_config() {
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "diagnostics": { "strict": true } }"#)
+        .a_file_with_content(CONTENT_WITH_WARNING)
+        .when_run()
+        .should_fail()
+        .expect_error("strict mode: 1 problem found");
+}
+
+#[test]
+fn suppressed_codes_should_neither_warn_nor_fail_strict_mode() {
+    Cmd::given()
+        .strict_flag()
+        .a_config_file_with_content(r#"{ "diagnostics": { "suppress": [{ "code": "TT008" }] } }"#)
+        .a_file_with_content(CONTENT_WITH_WARNING)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn suppressed_files_should_not_be_warned_about() {
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "diagnostics": { "suppress": [{ "file": "archive" }] } }"#)
+        .a_directory_containing_files(&[
+            ("archive/2024.md", "## TT 2024-01-15\n- #prj-1 Task A"),
+            ("2025.md", "## TT 2025-01-15\n- #prj-2 Task B"),
+        ])
+        .when_run()
+        .should_succeed()
+        .expect_warning_with_file("2025.md", "missing time")
+        .expect_no_error("archive/2024.md");
+}
+
+#[test]
+fn check_should_fail_on_warnings_in_strict_mode() {
+    Cmd::given()
+        .check_command()
+        .strict_flag()
+        .a_file_with_content(CONTENT_WITH_WARNING)
+        .when_run()
+        .should_fail()
+        .expect_output("0 errors, 1 warning");
+}
@@ -0,0 +1,122 @@
+use crate::common::Cmd;
+
+#[test]
+fn subtasks_should_add_up_when_entry_has_no_time() {
+    let content = r"## TT 2020-01-01
+- #dev Release
+  - 1h write tests
+  - 30m review";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("dev")
+        .taking("1h 30m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn indented_bullet_after_code_block_should_not_be_a_subtask() {
+    let content = r"## TT 2020-01-01
+- #dev Release
+  - 1h write tests
+```
+make release
+```
+  - 2h from the build log";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("dev")
+        .taking("1h 00m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn subtasks_should_be_part_of_entry_time() {
+    let content = r"## TT 2020-01-01
+- #dev 2h Refactor
+  - 30m extract module
+- #ops 1h Deploy";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("dev")
+        .taking("2h 00m")
+        .validate()
+        .expect_project("ops")
+        .taking("1h 00m")
+        .validate();
+}
+
+#[test]
+fn subtasks_should_warn_when_exceeding_entry_time() {
+    let content = r"## TT 2020-01-01
+- #dev 1h Spike
+  - 2h research";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(2, "subtasks exceed entry time: - #dev 1h Spike");
+}
+
+#[test]
+fn details_should_show_subtasks_below_their_entry() {
+    let content = r"## TT 2020-01-01
+- #dev Release
+  - 1h write tests
+  - 30m review";
+
+    Cmd::given()
+        .details_flag()
+        .tags_filter(&["dev"])
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output("- Release............... 1h 30m (100%)")
+        .expect_output("  - write tests......... 1h 00m (67%)")
+        .expect_output("  - review.............. 0h 30m (33%)");
+}
+
+#[test]
+fn subtask_ranges_should_not_overlap_their_parent_range() {
+    let content = r"## TT 2020-01-01
+- #dev 09:00-11:00 Release
+  - 09:00-10:00 write tests
+  - 10:00-11:00 review";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("dev")
+        .taking("2h 00m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn subtask_ranges_should_warn_at_subtask_line_when_overlapping() {
+    let content = r"## TT 2020-01-01
+- #dev 09:00-10:00 Standup
+- #dev Release
+  - 09:30-10:30 write tests";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(
+            4,
+            "overlapping time ranges: 09:30-10:30 overlaps 09:00-10:00",
+        );
+}
@@ -0,0 +1,46 @@
+use crate::common::Cmd;
+
+#[test]
+fn tag_grammar_should_keep_issue_numbers_in_description_when_numeric_ignored() {
+    let content = r"## TT 2020-01-01
+- #prj-api 1h fix #4521";
+
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "parsing": { "tags": { "ignore-numeric": true } } }"#)
+        .details_flag()
+        .tags_filter(&["prj-api"])
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_task_with_duration("fix #4521", "1h 00m");
+}
+
+#[test]
+fn tag_grammar_should_read_issue_numbers_as_tags_by_default() {
+    let content = r"## TT 2020-01-01
+- #prj-api 1h fix #4521";
+
+    Cmd::given()
+        .details_flag()
+        .tags_filter(&["prj-api"])
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_task_with_duration("fix", "1h 00m")
+        .expect_no_text("#4521");
+}
+
+#[test]
+fn tag_grammar_should_keep_tags_after_description_when_leading_only() {
+    let content = r"## TT 2020-01-01
+- #prj-api 1h fix #flaky test";
+
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "parsing": { "tags": { "leading-only": true } } }"#)
+        .details_flag()
+        .tags_filter(&["prj-api"])
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_task_with_duration("fix #flaky test", "1h 00m");
+}
@@ -0,0 +1,73 @@
+use crate::common::Cmd;
+
+const CONTENT: &str = r"## TT 2020-01-01
+- #client/acme/meeting 1h Kick-off
+- #client/acme/dev 2h Build API
+- #client/globex 30m Call
+- #admin 1h Mail";
+
+#[test]
+fn tags_filter_should_match_nested_tags() {
+    Cmd::given()
+        .tags_filter(&["client/acme"])
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_project("client/acme/dev")
+        .taking("2h 00m")
+        .validate()
+        .expect_project("client/acme/meeting")
+        .taking("1h 00m")
+        .validate()
+        .expect_no_text("globex")
+        .expect_no_text("admin");
+}
+
+#[test]
+fn overview_should_roll_up_tags_to_depth() {
+    Cmd::given()
+        .depth(2)
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("client................ 3h 30m ( 78%)")
+        .expect_output("  acme................ 3h 00m ( 67%)")
+        .expect_output("  globex.............. 0h 30m ( 11%)")
+        .expect_output("admin................. 1h 00m ( 22%)")
+        .expect_no_text("meeting");
+}
+
+#[test]
+fn overview_should_list_deepest_levels_when_depth_exceeds_tags() {
+    Cmd::given()
+        .depth(5)
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("    dev............... 2h 00m ( 44%)")
+        .expect_output("    meeting........... 1h 00m ( 22%)");
+}
+
+#[test]
+fn breakdown_should_roll_up_tags_to_depth() {
+    Cmd::given()
+        .depth(2)
+        .breakdown_flag("day")
+        .tags_filter(&["client"])
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("  - client..............")
+        .expect_output("    - acme................")
+        .expect_output("    - globex..............");
+}
+
+#[test]
+fn depth_should_fail_when_zero() {
+    Cmd::given()
+        .depth(0)
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_fail()
+        .expect_error("--depth must be at least 1");
+}
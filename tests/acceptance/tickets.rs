@@ -0,0 +1,88 @@
+use crate::common::*;
+
+const CONTENT: &str = r"## TT 2020-01-01
+- #prj-1 1h PROJ-12 fix login
+- #prj-1 30m login follow-up for PROJ-12
+- #prj-2 2h PROJ-7 reporting
+- #prj-2 15m standup";
+
+const JIRA_CONFIG: &str = r#"{ "parsing": { "ticket-pattern": "\\b[A-Z][A-Z0-9]+-\\d+\\b" } }"#;
+
+#[test]
+fn by_ticket_should_total_time_per_ticket_across_descriptions() {
+    Cmd::given()
+        .a_config_file_with_content(JIRA_CONFIG)
+        .by_ticket_flag()
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("PROJ-7................ 2h 00m")
+        .expect_output("PROJ-12............... 1h 30m")
+        .expect_output("<no ticket>........... 0h 15m");
+}
+
+#[test]
+fn by_ticket_details_should_list_tasks_per_ticket() {
+    Cmd::given()
+        .a_config_file_with_content(JIRA_CONFIG)
+        .by_ticket_flag()
+        .details_flag()
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("Project: PROJ-12")
+        .expect_task_with_duration("PROJ-12 fix login", "1h 00m")
+        .expect_task_with_duration("login follow-up for PROJ-12", "0h 30m");
+}
+
+#[test]
+fn by_ticket_should_use_configured_ticket_pattern() {
+    Cmd::given()
+        .a_config_file_with_content(
+            r##"{ "parsing": { "ticket-pattern": "#(\\d+)", "tags": { "ignore-numeric": true } } }"##,
+        )
+        .by_ticket_flag()
+        .a_file_with_content("## TT 2020-01-01\n- #prj-1 1h fix #42\n- #prj-1 1h #42 tests")
+        .when_run()
+        .should_succeed()
+        .expect_output("42.................... 2h 00m");
+}
+
+#[test]
+fn by_ticket_markdown_should_link_tickets_when_ticket_url_configured() {
+    Cmd::given()
+        .a_config_file_with_content(
+            r#"{ "parsing": { "ticket-pattern": "\\b[A-Z][A-Z0-9]+-\\d+\\b" },
+                 "reporting": { "ticket-url": "https://jira.example.com/browse/{ticket}" } }"#,
+        )
+        .by_ticket_flag()
+        .output_format("markdown")
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_succeed()
+        .expect_output("- **[PROJ-7](https://jira.example.com/browse/PROJ-7)**:  2h 00m")
+        .expect_output("- **<no ticket>**:  0h 15m");
+}
+
+#[test]
+fn ticket_url_should_fail_without_placeholder() {
+    Cmd::given()
+        .a_config_file_with_content(
+            r#"{ "reporting": { "ticket-url": "https://jira.example.com/browse/" } }"#,
+        )
+        .by_ticket_flag()
+        .a_file_with_content(CONTENT)
+        .when_run()
+        .should_fail()
+        .expect_error("ticket URL should contain {ticket}");
+}
+
+#[test]
+fn by_ticket_should_fail_without_ticket_pattern() {
+    Cmd::given()
+        .by_ticket_flag()
+        .a_file_with_content("## TT 2020-01-01\n- #prj-1 1h UTF-8 fix for ISO-8601 dates")
+        .when_run()
+        .should_fail()
+        .expect_error("--by-ticket needs a \"ticket-pattern\" in the parsing settings");
+}
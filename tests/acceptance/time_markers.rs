@@ -0,0 +1,95 @@
+use crate::common::Cmd;
+
+#[test]
+fn time_marker_should_warn_when_multiple_durations_by_default() {
+    let content = r"## TT 2020-01-01
+- #prj-1 1h fix 2h timeout bug";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("3h 00m")
+        .validate()
+        .expect_warning_at_line(2, "multiple durations: - #prj-1 1h fix 2h timeout bug");
+}
+
+#[test]
+fn time_marker_should_not_warn_when_duration_written_in_parts() {
+    let content = r"## TT 2020-01-01
+- #prj-1 1h 30m design review";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .strict_flag()
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("1h 30m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn time_marker_first_should_only_count_first_duration() {
+    let content = r"## TT 2020-01-01
+- #prj-1 1h fix 2h timeout bug";
+
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "parsing": { "time-marker": "first" } }"#)
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate();
+}
+
+#[test]
+fn time_marker_sigil_should_only_count_marked_time() {
+    let content = r"## TT 2020-01-01
+- #prj-1 fix 2h timeout bug @1h";
+
+    Cmd::given()
+        .a_config_file_with_content(r#"{ "parsing": { "time-marker": "sigil" } }"#)
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn time_marker_directive_should_apply_to_rest_of_file() {
+    let content = r"## TT 2020-01-01
+- #prj-1 1h Task A
+<!-- tt: time-marker = sigil -->
+- #prj-1 2h Task B @30m";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("1h 30m")
+        .validate();
+}
+
+#[test]
+fn time_marker_directive_should_warn_when_invalid() {
+    let content = r"<!-- tt: time-marker = sometimes -->
+## TT 2020-01-01
+- #prj-1 1h Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-1")
+        .taking("1h 00m")
+        .validate()
+        .expect_warning_at_line(1, "invalid directive: time-marker = sometimes");
+}
@@ -0,0 +1,111 @@
+use crate::common::Cmd;
+
+#[test]
+fn time_range_should_count_minutes_between_start_and_end() {
+    let content = r"## TT 2020-01-01
+- #prj-alpha 09:15-10:45 design review
+- #prj-beta 30m";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-alpha")
+        .taking("1h 30m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn time_range_should_count_minutes_when_crossing_midnight() {
+    let content = r"## TT 2020-01-01
+- #ops 23:00-01:30 release";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("ops")
+        .taking("2h 30m")
+        .validate();
+}
+
+#[test]
+fn time_range_should_warn_when_overlapping_within_same_day() {
+    let content = r"## TT 2020-01-01
+- #prj-alpha 09:00-10:30 design review
+- #prj-beta 10:00-11:00 standup";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-beta")
+        .taking("1h 00m")
+        .validate()
+        .expect_warning_at_line(
+            3,
+            "overlapping time ranges: 10:00-11:00 overlaps 09:00-10:30",
+        );
+}
+
+#[test]
+fn time_range_should_not_warn_when_overlapping_on_different_days() {
+    let content = r"## TT 2020-01-01
+- #prj-alpha 09:00-10:30
+## TT 2020-01-02
+- #prj-alpha 09:00-10:30";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_no_warnings();
+}
+
+#[test]
+fn time_range_should_not_warn_when_ranges_touch() {
+    let content = r"## TT 2020-01-01
+- #prj-alpha 09:00-10:00
+- #prj-alpha 10:00-11:00";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_no_warnings();
+}
+
+#[test]
+fn time_range_should_warn_when_overlapping_after_midnight() {
+    let content = r"## TT 2020-01-01
+- #ops 23:00-01:00 release
+- #ops 00:30-02:00 rollback";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(
+            3,
+            "overlapping time ranges: 00:30-02:00 overlaps 23:00-01:00",
+        );
+}
+
+#[test]
+fn time_range_should_warn_when_overlapping_range_of_another_file() {
+    Cmd::given()
+        .a_directory_containing_files(&[
+            ("b.md", "## TT 2025-01-15\n- #prj-beta 09:30-10:30 standup"),
+            (
+                "a.md",
+                "## TT 2025-01-15\n- #prj-alpha 09:00-10:00 design review",
+            ),
+        ])
+        .when_run()
+        .should_succeed()
+        .expect_warning_with_file(
+            "b.md",
+            "overlapping time ranges: 09:30-10:30 overlaps 09:00-10:00",
+        );
+}
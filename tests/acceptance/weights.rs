@@ -0,0 +1,122 @@
+use crate::common::*;
+
+#[test]
+fn overview_should_split_time_over_weighted_outcomes() {
+    let content = r"## TT 2020-01-01
+- #prj-1 ##launch:70% ##hiring:30% 1h Task A
+- #prj-2 ##launch 1h Task B";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_outcome_with_duration("launch", "1h 42m")
+        .expect_outcome_with_duration("hiring", "0h 18m");
+}
+
+#[test]
+fn overview_should_split_time_over_weighted_tags() {
+    let content = r"## TT 2020-01-01
+- #prj-a*2 #prj-b*2 1h Task A
+- #prj-c 1h Task C";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("prj-c")
+        .taking("1h 00m")
+        .with_percentage("50")
+        .expect_project("prj-a")
+        .taking("0h 30m")
+        .with_percentage("25")
+        .expect_project("prj-b")
+        .taking("0h 30m")
+        .with_percentage("25")
+        .validate();
+}
+
+#[test]
+fn overview_should_keep_total_when_split_does_not_divide_evenly() {
+    let content = r"## TT 2020-01-01
+- #prj-a*1 #prj-b*1 #prj-c*1 10m Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_output("0h 10m total")
+        .expect_project("prj-a")
+        .taking("0h 04m")
+        .expect_project("prj-b")
+        .taking("0h 03m")
+        .expect_project("prj-c")
+        .taking("0h 03m")
+        .validate();
+}
+
+#[test]
+fn entry_should_be_skipped_with_warning_when_percentages_do_not_add_up() {
+    let content = r"## TT 2020-01-01
+- #prj-1 ##launch:70% ##hiring:20% 1h Task A
+- #prj-2 1h Task B";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(
+            2,
+            "weights must be all percentages adding up to 100% or all shares",
+        )
+        .expect_project("prj-2")
+        .taking("1h 00m")
+        .validate();
+}
+
+#[test]
+fn details_should_count_the_tags_part_of_weighted_entries() {
+    let content = r"## TT 2020-01-01
+- #prj-a*3 #prj-b*1 2h Task A
+- #prj-a 1h Task B";
+
+    Cmd::given()
+        .details_flag()
+        .tags_filter(&["prj-a"])
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_task_with_duration("Task A", "1h 30m")
+        .expect_task_with_duration("Task B", "1h 00m")
+        .expect_output("2h 30m total");
+}
+
+#[test]
+fn tag_with_colon_and_number_should_not_be_weighted() {
+    let content = r"## TT 2020-01-01
+- #sprint:12 1h Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_project("sprint:12")
+        .taking("1h 00m")
+        .validate()
+        .expect_no_warnings();
+}
+
+#[test]
+fn entry_should_warn_when_only_some_tags_weighted() {
+    let content = r"## TT 2020-01-01
+- #dev #prj-a*1 #prj-b*1 1h Task A";
+
+    Cmd::given()
+        .a_file_with_content(content)
+        .when_run()
+        .should_succeed()
+        .expect_warning_at_line(2, "only some tags are weighted: - #dev #prj-a\\*1")
+        .expect_project("prj-a")
+        .taking("0h 30m")
+        .validate();
+}
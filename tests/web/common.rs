@@ -7,6 +7,7 @@ use axum::body::Body;
 use axum::http::{Method, Request, StatusCode};
 use chrono::NaiveDate;
 use std::sync::{Arc, Mutex, OnceLock};
+use time_tracker::config::Config;
 use tower::util::ServiceExt;
 
 // Global mutex to serialize access to TT_TODAY environment variable
@@ -41,6 +42,7 @@ impl WebApp {
 pub struct WebAppSpec {
     input: Option<InputSource>,
     run_date: Option<NaiveDate>,
+    config: Option<String>,
 }
 
 impl WebAppSpec {
@@ -48,9 +50,16 @@ impl WebAppSpec {
         Self {
             input: None,
             run_date: None,
+            config: None,
         }
     }
 
+    #[allow(dead_code)]
+    pub fn a_config_file_with_content(mut self, content: &str) -> Self {
+        self.config = Some(content.to_string());
+        self
+    }
+
     pub fn a_file_with_content(mut self, content: &str) -> Self {
         self.input = Some(InputSource::file(content));
         self
@@ -88,6 +97,7 @@ impl RequestBuilder {
         self
     }
 
+    #[allow(clippy::await_holding_lock)]
     pub async fn execute(self) -> WebAppResult {
         let (temp_dir, _input_path) = if let Some(input) = self.spec.input {
             let temp =
@@ -115,9 +125,19 @@ impl RequestBuilder {
             std::env::remove_var("TT_TODAY");
         }
 
+        let config = self.spec.config.map_or_else(Config::default, |content| {
+            let config_file =
+                assert_fs::NamedTempFile::new("config.json").expect("Failed to create config file");
+            config_file
+                .write_str(&content)
+                .expect("Failed to write config file");
+            Config::load(config_file.path()).expect("Invalid config in test")
+        });
+
         let state = std::sync::Arc::new(time_tracker::web::AppState {
             data_path: _input_path,
             clock: time_tracker::web::handlers::create_clock(),
+            config,
         });
         let app = time_tracker::web::server::create_router_with_state(state);
 
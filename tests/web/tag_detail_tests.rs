@@ -50,6 +50,22 @@ async fn tag_detail_should_accept_tags_with_dashes_and_underscores() {
         .expect_contains("Work with valid tag chars");
 }
 
+#[tokio::test]
+async fn tag_detail_should_include_entries_of_nested_tags() {
+    WebApp::given()
+        .a_file_with_content(
+            "## TT 2025-01-15\n\
+             - #client/acme/meeting 1h Kick-off\n\
+             - #client/acme 2h Proposal\n",
+        )
+        .when_get("/api/tag/client%2Facme")
+        .should_succeed()
+        .await
+        .expect_status(200)
+        .expect_contains("Kick-off")
+        .expect_contains("Proposal");
+}
+
 #[tokio::test]
 async fn tag_detail_should_respect_period_filter() {
     WebApp::given()
@@ -247,3 +263,71 @@ async fn tag_detail_should_respect_this_month_filter() {
         .expect_contains("This month")
         .expect_not_contains("Last month");
 }
+
+#[tokio::test]
+async fn tag_detail_should_link_tickets_when_ticket_url_configured() {
+    WebApp::given()
+        .a_config_file_with_content(
+            r#"{ "parsing": { "ticket-pattern": "\\b[A-Z][A-Z0-9]+-\\d+\\b" },
+                 "reporting": { "ticket-url": "https://jira.example.com/browse/{ticket}" } }"#,
+        )
+        .a_file_with_content(
+            "## TT 2025-01-15\n\
+             - #project-alpha 2h PROJ-12 fix login\n",
+        )
+        .when_get("/api/tag/project-alpha")
+        .should_succeed()
+        .await
+        .expect_status(200)
+        .expect_contains(
+            r#"<a class="ticket" href="https://jira.example.com/browse/PROJ-12">PROJ-12</a>"#,
+        );
+}
+
+#[tokio::test]
+async fn tag_detail_should_show_tickets_without_link_when_no_ticket_url() {
+    WebApp::given()
+        .a_config_file_with_content(
+            r#"{ "parsing": { "ticket-pattern": "\\b[A-Z][A-Z0-9]+-\\d+\\b" } }"#,
+        )
+        .a_file_with_content(
+            "## TT 2025-01-15\n\
+             - #project-alpha 2h PROJ-12 fix login\n",
+        )
+        .when_get("/api/tag/project-alpha")
+        .should_succeed()
+        .await
+        .expect_status(200)
+        .expect_contains(r#"<span class="ticket">PROJ-12</span>"#)
+        .expect_not_contains("href=");
+}
+
+#[tokio::test]
+async fn tag_detail_should_show_date_and_source_line_of_entries() {
+    WebApp::given()
+        .a_file_with_content(
+            "## TT 2025-01-15\n\
+             - #project-alpha 2h Building dashboard\n",
+        )
+        .when_get("/api/tag/project-alpha")
+        .should_succeed()
+        .await
+        .expect_status(200)
+        .expect_contains("<td>2025-01-15</td>")
+        .expect_contains(".md:2</td>");
+}
+
+#[tokio::test]
+async fn tag_detail_should_show_no_tickets_without_ticket_pattern() {
+    WebApp::given()
+        .a_file_with_content(
+            "## TT 2025-01-15\n\
+             - #project-alpha 2h UTF-8 fix for ISO-8601 dates\n",
+        )
+        .when_get("/api/tag/project-alpha")
+        .should_succeed()
+        .await
+        .expect_status(200)
+        .expect_contains("UTF-8 fix for ISO-8601 dates")
+        .expect_not_contains(r#"class="ticket""#);
+}